@@ -1,3 +1,4 @@
+pub mod reqwest_http_fetcher;
 pub mod rfd_file_picker;
 pub mod rfd_folder_picker;
 
@@ -0,0 +1,110 @@
+use crate::ports::{HttpConfig, HttpError, HttpFetcher, HttpRequest, HttpResponse};
+use reqwest::blocking::Client;
+use std::sync::RwLock;
+use std::time::Duration;
+
+const DEFAULT_USER_AGENT: &str = concat!("RCast/", env!("CARGO_PKG_VERSION"));
+
+struct Clients {
+    following: Client,
+    // Same settings, but hands 3xx responses back to the caller.
+    manual_redirects: Client,
+}
+
+pub struct ReqwestHttpFetcher {
+    clients: RwLock<Clients>,
+}
+
+impl ReqwestHttpFetcher {
+    // Must be called outside the async runtime (the blocking client owns its own).
+    pub fn new() -> Self {
+        let clients =
+            build_clients(&HttpConfig::default()).expect("default HTTP client configuration");
+        Self {
+            clients: RwLock::new(clients),
+        }
+    }
+}
+
+impl HttpFetcher for ReqwestHttpFetcher {
+    fn get(&self, request: HttpRequest) -> Result<HttpResponse, HttpError> {
+        let client = {
+            let clients = self.clients.read().unwrap();
+            if request.follow_redirects {
+                clients.following.clone()
+            } else {
+                clients.manual_redirects.clone()
+            }
+        };
+
+        let mut builder = client.get(&request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder.send().map_err(map_error)?;
+
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str().to_lowercase(), value.to_str().ok()?.to_string()))
+            })
+            .collect();
+
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            headers,
+            body: Box::new(response),
+        })
+    }
+
+    fn configure(&self, config: HttpConfig) -> Result<(), HttpError> {
+        let clients = build_clients(&config)?;
+        *self.clients.write().unwrap() = clients;
+        Ok(())
+    }
+}
+
+fn build_clients(config: &HttpConfig) -> Result<Clients, HttpError> {
+    Ok(Clients {
+        following: build_client(config, reqwest::redirect::Policy::default())?,
+        manual_redirects: build_client(config, reqwest::redirect::Policy::none())?,
+    })
+}
+
+fn build_client(config: &HttpConfig, redirects: reqwest::redirect::Policy) -> Result<Client, HttpError> {
+    let mut builder = Client::builder()
+        .redirect(redirects)
+        .user_agent(config.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs.unwrap_or(15)))
+        // The blocking client applies this per read, so long downloads are unaffected.
+        .timeout(Duration::from_secs(config.read_timeout_secs.unwrap_or(30)));
+
+    if let Some(proxy) = &config.proxy {
+        let proxy = reqwest::Proxy::all(proxy)
+            .map_err(|e| HttpError::Config(format!("proxy: {e}")))?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = &config.ca_bundle_path {
+        let pem = std::fs::read(path)
+            .map_err(|e| HttpError::Config(format!("CA bundle {path}: {e}")))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| HttpError::Config(format!("CA bundle {path}: {e}")))?;
+        builder = builder.tls_certs_merge(certs);
+    }
+
+    builder.build().map_err(|e| HttpError::Config(e.to_string()))
+}
+
+fn map_error(e: reqwest::Error) -> HttpError {
+    if e.is_timeout() {
+        HttpError::Timeout
+    } else if e.is_connect() {
+        HttpError::Connect(e.to_string())
+    } else if let Some(status) = e.status() {
+        HttpError::Status(status.as_u16())
+    } else {
+        HttpError::Other(e.to_string())
+    }
+}
@@ -4,13 +4,14 @@ use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use crate::audio_player::{AudioPlayer, PlaybackState};
 use crate::commands::AppCommand;
 use crate::components::add_podcast_modal::AddPodcastModal;
+use crate::components::merge_podcasts_modal::MergePodcastsModal;
 use crate::components::notes_panel::NotesPanel;
 use crate::components::toast;
 use crate::db::models::DownloadStatus;
 use crate::events::AppEvent;
 use crate::hotkeys::HotkeyManager;
 use crate::pages::{home::HomePage, podcast_detail::PodcastDetailPage, settings::SettingsPage};
-use crate::ports::{FilePicker, FolderPicker};
+use crate::ports::{FilePicker, FolderPicker, HttpFetcher};
 use crate::design::{visuals::build_visuals, ThemeTokens};
 use crate::state::AppState;
 use crate::tray::AppTray;
@@ -25,6 +26,7 @@ pub struct RCast {
     pub audio_player: AudioPlayer,
 
     pub add_podcast_modal: AddPodcastModal,
+    pub merge_podcasts_modal: MergePodcastsModal,
 
     pub notes_panel: NotesPanel,
 
@@ -37,29 +39,38 @@ pub struct RCast {
     last_theme: crate::types::ThemeMode,
 }
 
+// The ports the UI itself calls into.
+pub struct UiPorts {
+    pub folder_picker: Arc<dyn FolderPicker>,
+    pub file_picker: Arc<dyn FilePicker>,
+    pub http: Arc<dyn HttpFetcher>,
+}
+
 impl RCast {
     pub fn new(
         cmd_tx: UnboundedSender<AppCommand>,
         event_rx: UnboundedReceiver<AppEvent>,
         audio_player: AudioPlayer,
-        folder_picker: Arc<dyn FolderPicker>,
-        file_picker: Arc<dyn FilePicker>,
+        ports: UiPorts,
         tray: Option<AppTray>,
         hotkeys: Option<HotkeyManager>,
     ) -> Self {
         let _ = cmd_tx.send(AppCommand::NavigateTo(Page::Home));
         let _ = cmd_tx.send(AppCommand::ClearQueue);
         let mut settings_page = SettingsPage::default();
-        settings_page.set_folder_picker(folder_picker);
-        settings_page.set_file_picker(file_picker);
+        settings_page.set_folder_picker(ports.folder_picker);
+        settings_page.set_file_picker(ports.file_picker);
+        let mut state = AppState::default();
+        state.image_cache.set_http_fetcher(ports.http);
 
         Self {
             cmd_tx,
             event_rx,
-            state: AppState::default(),
+            state,
             current_page: Page::Home,
             audio_player,
             add_podcast_modal: AddPodcastModal::new(),
+            merge_podcasts_modal: MergePodcastsModal::new(),
             notes_panel: NotesPanel::default(),
             home_page: HomePage::default(),
             podcast_detail_page: PodcastDetailPage::default(),
@@ -94,6 +105,34 @@ impl RCast {
             AppEvent::PodcastRemoved(id) => {
                 self.state.podcasts.retain(|p| p.id != id);
             }
+            AppEvent::PodcastUpdated(podcast) => {
+                let old_image = self
+                    .state
+                    .podcasts
+                    .iter()
+                    .chain(self.state.detail_podcast.as_ref())
+                    .find(|p| p.id == podcast.id)
+                    .map(|p| p.image_url.clone());
+                // The cache is keyed by URL without its query string, so artwork swapped
+                // behind a cache-busting parameter would otherwise keep the old image.
+                if let Some(old_image) = old_image
+                    && old_image != podcast.image_url
+                {
+                    self.state.image_cache.invalidate(&old_image);
+                    self.state.image_cache.invalidate(&podcast.image_url);
+                }
+                if let Some(p) = self.state.podcasts.iter_mut().find(|p| p.id == podcast.id) {
+                    *p = podcast.clone();
+                }
+                if let Some(p) = self.state.detail_podcast.as_mut()
+                    && p.id == podcast.id
+                {
+                    *p = podcast;
+                }
+            }
+            AppEvent::FeedMoveConflict { moved, existing } => {
+                self.merge_podcasts_modal.offer(*moved, *existing);
+            }
             AppEvent::PodcastDetailLoaded { podcast, episodes } => {
                 self.state.detail_podcast = Some(podcast);
                 self.state.detail_episodes = episodes;
@@ -105,6 +144,16 @@ impl RCast {
                 if let Some(p) = self.state.podcasts.iter_mut().find(|p| p.id == podcast_id) {
                     p.episode_count = episodes.len() as i32;
                 }
+                // Pick up corrected feed metadata for the playing episode; the live
+                // position and stream URL stay as they are.
+                if let Some(playing) = self.state.now_playing_episode.as_mut()
+                    && let Some(fresh) = episodes.iter().find(|e| e.id == playing.id)
+                {
+                    playing.title = fresh.title.clone();
+                    playing.description = fresh.description.clone();
+                    playing.duration = fresh.duration;
+                    playing.publish_date = fresh.publish_date;
+                }
                 if self
                     .state
                     .detail_podcast
@@ -130,8 +179,12 @@ impl RCast {
                     p.speed_preset = prefs.speed_preset;
                     p.auto_download = prefs.auto_download;
                     p.keep_episodes_count = prefs.keep_episodes_count;
+                    p.delete_played_after_days = prefs.delete_played_after_days;
+                    p.delete_older_than_days = prefs.delete_older_than_days;
+                    p.keep_unplayed = prefs.keep_unplayed;
                     p.skip_intro_seconds = prefs.skip_intro_seconds;
                     p.skip_outro_seconds = prefs.skip_outro_seconds;
+                    p.sync_interval_minutes = prefs.sync_interval_minutes;
                 }
             }
 
@@ -140,6 +193,7 @@ impl RCast {
                 episode_id,
                 status,
                 path,
+                reason,
             } => {
                 for ep in self.state.detail_episodes.iter_mut() {
                     if ep.id == episode_id {
@@ -152,16 +206,29 @@ impl RCast {
                         break;
                     }
                 }
-                let label = match status {
-                    DownloadStatus::Downloading => None,
-                    DownloadStatus::Downloaded => Some("Downloaded"),
-                    DownloadStatus::Failed => Some("Download failed"),
-                    DownloadStatus::NotDownloaded => None,
-                };
-                if let Some(msg) = label && status == DownloadStatus::Failed {
-                    self.state.toasts.push(toast::ToastMessage::error(msg));
+                if status != DownloadStatus::Downloading {
+                    self.state.download_progress.remove(&episode_id);
+                }
+                if status == DownloadStatus::Failed {
+                    let msg = match &reason {
+                        Some(reason) => format!("Download failed: {reason}"),
+                        None => "Download failed".to_string(),
+                    };
+                    self.state.toasts.push(toast::ToastMessage::error(&msg));
+                    if let Some(reason) = reason {
+                        self.state.download_errors.insert(episode_id, reason);
+                    }
+                } else {
+                    self.state.download_errors.remove(&episode_id);
                 }
             }
+            AppEvent::DownloadProgress {
+                episode_id,
+                bytes,
+                total,
+            } => {
+                self.state.download_progress.insert(episode_id, (bytes, total));
+            }
 
             // Queue
             AppEvent::QueueUpdated(items) => {
@@ -289,6 +356,27 @@ impl RCast {
             AppEvent::ListeningStatsLoaded(stats) => {
                 self.state.listening_stats = Some(stats);
             }
+            AppEvent::NetworkAuditLoaded(entries) => {
+                self.state.network_audit = entries;
+            }
+            AppEvent::StorageUsageLoaded(usage) => {
+                self.state.storage_usage = usage;
+            }
+            AppEvent::DownloadsReconciled(unknown) => {
+                self.state.unknown_downloads = unknown;
+            }
+            AppEvent::MissingDownloadsHeld(episode_ids) => {
+                self.state.held_missing_downloads = episode_ids;
+            }
+            AppEvent::AudioCacheLoaded(stats) => {
+                self.state.audio_cache = stats;
+            }
+            AppEvent::LibraryMoveProgress { done, total } => {
+                self.state.library_move = Some((done, total));
+            }
+            AppEvent::LibraryMoveFinished => {
+                self.state.library_move = None;
+            }
 
             // Cross-cutting
             AppEvent::Toast(msg) => {
@@ -545,6 +633,16 @@ impl eframe::App for RCast {
             let _ = self.cmd_tx.send(AppCommand::AddPodcast { feed_url: url });
         }
 
+        // Merge offer after a feed move
+        if let Some((from_id, into_id)) =
+            self.merge_podcasts_modal.render(&ctx, &self.state.theme)
+        {
+            let _ = self.cmd_tx.send(AppCommand::MergePodcasts { from_id, into_id });
+            if self.current_page == Page::PodcastDetail(from_id) {
+                let _ = self.cmd_tx.send(AppCommand::NavigateTo(Page::PodcastDetail(into_id)));
+            }
+        }
+
         // Toast overlay
         toast::render(&ctx, &mut self.state.toasts, &self.state.theme);
     }
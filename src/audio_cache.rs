@@ -1,49 +1,246 @@
 use bytes::Bytes;
+use dirs::data_local_dir;
 use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
 
+use crate::types::AudioCacheStats;
+
+/// Audio fetched for playback (prefetched episodes, mostly), kept so it plays again without
+/// going back to the network.
+///
+/// Entries are held in memory up to the memory budget. Past that, the least recently used
+/// ones are spilled to files under the data directory, up to the disk budget, and dropped
+/// after that. An entry larger than the memory budget goes to disk straight away. The spill
+/// directory is only an extension of memory: it is emptied on startup.
+///
+/// The cache lives behind a mutex that playback also takes, so the methods that evict entries
+/// hand back `Spill`s instead of writing them; `write_spills` does that without the lock.
 pub struct AudioCache {
-    map: HashMap<i32, Bytes>,
+    memory: HashMap<i32, Bytes>,
     // Front = most recently used, back = least recently used.
-    order: VecDeque<i32>,
-    max_entries: usize,
+    memory_order: VecDeque<i32>,
+    memory_bytes: u64,
+    memory_budget: u64,
+    // Size of each spilled file.
+    disk: HashMap<i32, u64>,
+    disk_order: VecDeque<i32>,
+    disk_bytes: u64,
+    disk_budget: u64,
+    dir: PathBuf,
+    // Bumped by `clear`, so spills written while it ran are not taken back.
+    generation: u64,
+}
+
+/// An entry evicted from memory that still has to be written to disk.
+pub struct Spill {
+    episode_id: i32,
+    bytes: Bytes,
+    path: PathBuf,
+    generation: u64,
+}
+
+pub enum CachedAudio {
+    Memory(Bytes),
+    File(PathBuf),
 }
 
 impl AudioCache {
-    pub fn new(max_entries: usize) -> Self {
+    pub fn new(memory_budget: u64, disk_budget: u64) -> Self {
+        let dir = audio_cache_dir();
+        // Nothing indexes files left by the last run.
+        let _ = fs::remove_dir_all(&dir);
         Self {
-            map: HashMap::new(),
-            order: VecDeque::new(),
-            max_entries,
+            memory: HashMap::new(),
+            memory_order: VecDeque::new(),
+            memory_bytes: 0,
+            memory_budget,
+            disk: HashMap::new(),
+            disk_order: VecDeque::new(),
+            disk_bytes: 0,
+            disk_budget,
+            dir,
+            generation: 0,
         }
     }
 
-    pub fn get(&mut self, episode_id: i32) -> Option<Bytes> {
-        if self.map.contains_key(&episode_id) {
-            self.promote(episode_id);
-            self.map.get(&episode_id).cloned()
-        } else {
-            None
+    pub fn get(&mut self, episode_id: i32) -> Option<CachedAudio> {
+        if let Some(bytes) = self.memory.get(&episode_id).cloned() {
+            promote(&mut self.memory_order, episode_id);
+            return Some(CachedAudio::Memory(bytes));
+        }
+        if self.disk.contains_key(&episode_id) {
+            let path = self.spill_path(episode_id);
+            if path.exists() {
+                promote(&mut self.disk_order, episode_id);
+                return Some(CachedAudio::File(path));
+            }
+            self.remove_from_disk(episode_id);
+        }
+        None
+    }
+
+    pub fn contains(&self, episode_id: i32) -> bool {
+        self.memory.contains_key(&episode_id) || self.disk.contains_key(&episode_id)
+    }
+
+    pub fn insert(&mut self, episode_id: i32, bytes: Bytes) -> Vec<Spill> {
+        self.remove(episode_id);
+
+        let len = bytes.len() as u64;
+        if len > self.memory_budget {
+            return self.spill(episode_id, bytes).into_iter().collect();
         }
+        self.memory.insert(episode_id, bytes);
+        self.memory_order.push_front(episode_id);
+        self.memory_bytes += len;
+        self.enforce_budgets()
+    }
+
+    // Applies new budgets straight away, spilling or dropping whatever no longer fits.
+    pub fn set_budgets(&mut self, memory_budget: u64, disk_budget: u64) -> Vec<Spill> {
+        self.memory_budget = memory_budget;
+        self.disk_budget = disk_budget;
+        self.enforce_budgets()
     }
 
-    pub fn insert(&mut self, episode_id: i32, bytes: Bytes) {
-        if self.map.contains_key(&episode_id) {
-            // Already present — just promote and update.
-            self.promote(episode_id);
-            self.map.insert(episode_id, bytes);
+    // Forgets every entry. Returns the spill directory, for the caller to delete once the lock
+    // is released.
+    pub fn clear(&mut self) -> PathBuf {
+        self.memory.clear();
+        self.memory_order.clear();
+        self.memory_bytes = 0;
+        self.disk.clear();
+        self.disk_order.clear();
+        self.disk_bytes = 0;
+        self.generation += 1;
+        self.dir.clone()
+    }
+
+    // Takes back an entry whose file `write_spills` has written. The file is deleted instead
+    // if the entry was cleared or inserted again in the meantime.
+    fn spilled(&mut self, spill: Spill) {
+        if spill.generation != self.generation || self.memory.contains_key(&spill.episode_id) {
+            let _ = fs::remove_file(&spill.path);
             return;
         }
+        if self.disk.contains_key(&spill.episode_id) {
+            return;
+        }
+        let len = spill.bytes.len() as u64;
+        self.disk.insert(spill.episode_id, len);
+        self.disk_order.push_front(spill.episode_id);
+        self.disk_bytes += len;
+        self.trim_disk();
+    }
+
+    pub fn stats(&self) -> AudioCacheStats {
+        AudioCacheStats {
+            memory_entries: self.memory.len(),
+            memory_bytes: self.memory_bytes,
+            disk_entries: self.disk.len(),
+            disk_bytes: self.disk_bytes,
+        }
+    }
+
+    fn enforce_budgets(&mut self) -> Vec<Spill> {
+        let mut spills = Vec::new();
+        while self.memory_bytes > self.memory_budget
+            && let Some(lru_id) = self.memory_order.pop_back()
+        {
+            if let Some(bytes) = self.memory.remove(&lru_id) {
+                self.memory_bytes -= bytes.len() as u64;
+                spills.extend(self.spill(lru_id, bytes));
+            }
+        }
+        self.trim_disk();
+        spills
+    }
+
+    fn trim_disk(&mut self) {
+        while self.disk_bytes > self.disk_budget
+            && let Some(&lru_id) = self.disk_order.back()
+        {
+            self.remove_from_disk(lru_id);
+        }
+    }
+
+    // Entries that don't fit the disk budget either are dropped.
+    fn spill(&self, episode_id: i32, bytes: Bytes) -> Option<Spill> {
+        if bytes.len() as u64 > self.disk_budget {
+            return None;
+        }
+        Some(Spill {
+            episode_id,
+            bytes,
+            path: self.spill_path(episode_id),
+            generation: self.generation,
+        })
+    }
+
+    fn remove(&mut self, episode_id: i32) {
+        if let Some(bytes) = self.memory.remove(&episode_id) {
+            self.memory_bytes -= bytes.len() as u64;
+            self.memory_order.retain(|&id| id != episode_id);
+        }
+        self.remove_from_disk(episode_id);
+    }
 
-        if self.map.len() >= self.max_entries && let Some(lru_id) = self.order.pop_back() {
-            self.map.remove(&lru_id);
+    fn remove_from_disk(&mut self, episode_id: i32) {
+        if let Some(len) = self.disk.remove(&episode_id) {
+            self.disk_bytes -= len;
+            self.disk_order.retain(|&id| id != episode_id);
+            // A file still open for playback may refuse to go; startup clears it then.
+            let _ = fs::remove_file(self.spill_path(episode_id));
         }
+    }
 
-        self.map.insert(episode_id, bytes);
-        self.order.push_front(episode_id);
+    fn spill_path(&self, episode_id: i32) -> PathBuf {
+        self.dir.join(format!("{episode_id}.audio"))
     }
+}
 
-    fn promote(&mut self, episode_id: i32) {
-        self.order.retain(|&id| id != episode_id);
-        self.order.push_front(episode_id);
+/// Writes evicted entries to disk, then hands them back to the cache. Call without holding
+/// the cache's lock.
+pub fn write_spills(cache: &Mutex<AudioCache>, spills: Vec<Spill>) {
+    for spill in spills {
+        let written = spill
+            .path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&spill.path, &spill.bytes));
+        match written {
+            Ok(()) => cache.lock().unwrap().spilled(spill),
+            Err(e) => {
+                eprintln!(
+                    "[audio cache] could not spill episode {}: {e}",
+                    spill.episode_id
+                );
+                let _ = fs::remove_file(&spill.path);
+            }
+        }
     }
 }
+
+// Deletes the spill directory returned by `AudioCache::clear`.
+pub fn remove_spill_dir(dir: &std::path::Path) {
+    if let Err(e) = fs::remove_dir_all(dir)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        eprintln!("[audio cache] could not clear {}: {e}", dir.display());
+    }
+}
+
+fn promote(order: &mut VecDeque<i32>, episode_id: i32) {
+    order.retain(|&id| id != episode_id);
+    order.push_front(episode_id);
+}
+
+fn audio_cache_dir() -> PathBuf {
+    data_local_dir()
+        .unwrap()
+        .join("rcast")
+        .join("cache")
+        .join("audio")
+}
@@ -1,12 +1,17 @@
+use crate::http_stream::HttpStream;
+use crate::time_stretch::{StretchControl, TimeStretchSource};
 use crate::trim_silence::TrimSilenceSource;
 use crate::types::TrimSilenceMode;
 use bytes::Bytes;
 use rodio::{Decoder, DeviceSinkBuilder, MixerDeviceSink, Player, Source};
 use std::fs::File;
-use std::io::Cursor;
+use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+// Enough of a streamed file to find the first MPEG frame past a typical ID3 tag with artwork.
+const STREAM_HEAD_BYTES: u64 = 256 * 1024;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PlaybackState {
     Stopped,
@@ -22,6 +27,9 @@ pub struct AudioPlayer {
     state: Arc<Mutex<PlaybackState>>,
     duration: Arc<Mutex<Duration>>,
     trim_silence_mode: Arc<Mutex<TrimSilenceMode>>,
+    preserve_pitch: Arc<Mutex<bool>>,
+    // Set while the current source is time-stretched; speed changes then go through it.
+    stretch: Arc<Mutex<Option<Arc<StretchControl>>>>,
 }
 
 impl AudioPlayer {
@@ -33,6 +41,8 @@ impl AudioPlayer {
             state: Arc::new(Mutex::new(PlaybackState::Stopped)),
             duration: Arc::new(Mutex::new(Duration::from_secs(0))),
             trim_silence_mode: Arc::new(Mutex::new(TrimSilenceMode::Off)),
+            preserve_pitch: Arc::new(Mutex::new(true)),
+            stretch: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -40,6 +50,11 @@ impl AudioPlayer {
         *self.trim_silence_mode.lock().unwrap() = mode;
     }
 
+    // Takes effect from the next episode started.
+    pub fn set_preserve_pitch(&self, preserve_pitch: bool) {
+        *self.preserve_pitch.lock().unwrap() = preserve_pitch;
+    }
+
     pub fn play_from_file(&self, path: &str, episode_id: i32) -> Result<(), String> {
         let file = File::open(path).map_err(|e| e.to_string())?;
         let source = Decoder::new(file).map_err(|e| e.to_string())?;
@@ -47,7 +62,7 @@ impl AudioPlayer {
         let duration = source.total_duration().unwrap_or_else(|| {
             std::fs::read(path)
                 .ok()
-                .and_then(|b| estimate_duration(&b))
+                .and_then(|b| estimate_duration(&b, b.len()))
                 .unwrap_or(Duration::ZERO)
         });
 
@@ -61,7 +76,40 @@ impl AudioPlayer {
 
         let duration = source
             .total_duration()
-            .unwrap_or_else(|| estimate_duration(&bytes).unwrap_or(Duration::ZERO));
+            .unwrap_or_else(|| estimate_duration(&bytes, bytes.len()).unwrap_or(Duration::ZERO));
+
+        let boxed: Box<dyn Source<Item = f32> + Send + 'static> = Box::new(source);
+        self.start_source(boxed, episode_id, duration)
+    }
+
+    // Starts as soon as the decoder has probed the first bytes; the rest arrives while playing.
+    pub fn play_from_stream(&self, mut stream: HttpStream, episode_id: i32) -> Result<(), String> {
+        let mut head = Vec::new();
+        (&mut stream)
+            .take(STREAM_HEAD_BYTES)
+            .read_to_end(&mut head)
+            .map_err(|e| e.to_string())?;
+        stream.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+
+        let len = stream.len();
+        let mime_type = stream.content_type().map(str::to_string);
+        let mut builder = Decoder::builder().with_data(stream);
+        // Coarse seeks jump to an estimated byte offset instead of reading up to the target.
+        if let Some(len) = len {
+            builder = builder
+                .with_byte_len(len)
+                .with_seekable(true)
+                .with_coarse_seek(true);
+        }
+        if let Some(mime_type) = &mime_type {
+            builder = builder.with_mime_type(mime_type);
+        }
+        let source = builder.build().map_err(|e| e.to_string())?;
+
+        let duration = source.total_duration().unwrap_or_else(|| {
+            len.and_then(|len| estimate_duration(&head, len as usize))
+                .unwrap_or(Duration::ZERO)
+        });
 
         let boxed: Box<dyn Source<Item = f32> + Send + 'static> = Box::new(source);
         self.start_source(boxed, episode_id, duration)
@@ -85,20 +133,26 @@ impl AudioPlayer {
         let speed = *self.playback_speed.lock().unwrap();
         let trim_mode = *self.trim_silence_mode.lock().unwrap();
 
-        match trim_mode {
-            TrimSilenceMode::Off => {
-                new_player.append(source.speed(speed));
-            }
-            mode => {
-                new_player.append(TrimSilenceSource::new(source, mode).speed(speed));
-            }
-        }
+        let source: Box<dyn Source<Item = f32> + Send + 'static> = match trim_mode {
+            TrimSilenceMode::Off => source,
+            mode => Box::new(TrimSilenceSource::new(source, mode)),
+        };
+        // Time stretching keeps voices at their pitch; plain `speed` resamples.
+        let stretch = if *self.preserve_pitch.lock().unwrap() {
+            let control = Arc::new(StretchControl::new(speed));
+            new_player.append(TimeStretchSource::new(source, control.clone()));
+            Some(control)
+        } else {
+            new_player.append(source.speed(speed));
+            None
+        };
         new_player.play();
 
         *player_guard = Some((new_player, stream));
         *self.current_episode_id.lock().unwrap() = Some(episode_id);
         *self.state.lock().unwrap() = PlaybackState::Playing;
         *self.duration.lock().unwrap() = duration;
+        *self.stretch.lock().unwrap() = stretch;
 
         Ok(())
     }
@@ -121,6 +175,7 @@ impl AudioPlayer {
         if let Some((player, _)) = self.player.lock().unwrap().take() {
             player.stop();
         }
+        *self.stretch.lock().unwrap() = None;
         *self.current_episode_id.lock().unwrap() = None;
         *self.state.lock().unwrap() = PlaybackState::Stopped;
     }
@@ -133,7 +188,9 @@ impl AudioPlayer {
 
     pub fn set_speed(&self, speed: f32) {
         *self.playback_speed.lock().unwrap() = speed;
-        if let Some((player, _)) = self.player.lock().unwrap().as_ref() {
+        if let Some(control) = self.stretch.lock().unwrap().as_ref() {
+            control.set_speed(speed);
+        } else if let Some((player, _)) = self.player.lock().unwrap().as_ref() {
             player.set_speed(speed);
         }
     }
@@ -146,7 +203,7 @@ impl AudioPlayer {
 
     pub fn skip_forward(&self, seconds: i32) {
         if let Some((player, _)) = self.player.lock().unwrap().as_ref() {
-            let current = player.get_pos();
+            let current = self.position_of(player);
             let new_pos = current + Duration::from_secs(seconds as u64);
             player.try_seek(new_pos).ok();
         }
@@ -154,7 +211,7 @@ impl AudioPlayer {
 
     pub fn skip_backward(&self, seconds: i32) {
         if let Some((player, _)) = self.player.lock().unwrap().as_ref() {
-            let current = player.get_pos();
+            let current = self.position_of(player);
             let new_pos = current.saturating_sub(Duration::from_secs(seconds as u64));
             player.try_seek(new_pos).ok();
         }
@@ -162,12 +219,21 @@ impl AudioPlayer {
 
     pub fn get_position(&self) -> Duration {
         if let Some((player, _)) = self.player.lock().unwrap().as_ref() {
-            player.get_pos()
+            self.position_of(player)
         } else {
             Duration::from_secs(0)
         }
     }
 
+    // A time-stretched source keeps its own clock: the player's counts time played, which
+    // runs slower or faster than the episode.
+    fn position_of(&self, player: &Player) -> Duration {
+        match self.stretch.lock().unwrap().as_ref() {
+            Some(control) => control.position(),
+            None => player.get_pos(),
+        }
+    }
+
     pub fn get_state(&self) -> PlaybackState {
         *self.state.lock().unwrap()
     }
@@ -193,7 +259,8 @@ impl AudioPlayer {
     }
 }
 
-fn estimate_duration(bytes: &[u8]) -> Option<Duration> {
+// Reads the bitrate from the first MPEG frame in `bytes`, the start of a `len` byte file.
+fn estimate_duration(bytes: &[u8], len: usize) -> Option<Duration> {
     if bytes.len() < 4 {
         return None;
     }
@@ -237,7 +304,7 @@ fn estimate_duration(bytes: &[u8]) -> Option<Duration> {
     }
 
     let bytes_per_sec = (bitrate_kbps * 1000 / 8) as usize;
-    let secs = bytes.len() / bytes_per_sec;
+    let secs = len / bytes_per_sec;
 
     Some(Duration::from_secs(secs as u64))
 }
@@ -0,0 +1,79 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+// Longest single sleep, so a stopped download notices within this time.
+const MAX_WAIT: Duration = Duration::from_millis(250);
+
+/// Token bucket shared by every download, so the cap holds however many run at once.
+///
+/// The bucket refills at the configured rate and holds at most one second's worth, which
+/// allows a short burst after an idle period. A transfer that overdraws it waits until the
+/// balance is back to zero.
+pub struct BandwidthLimiter {
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    // Bytes per second; 0 = unlimited.
+    rate: u64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl BandwidthLimiter {
+    pub fn new() -> Self {
+        Self {
+            bucket: Mutex::new(Bucket {
+                rate: 0,
+                tokens: 0.0,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    // None removes the limit. Applies to running downloads straight away.
+    pub fn set_rate(&self, bytes_per_sec: Option<u64>) {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.rate = bytes_per_sec.unwrap_or(0);
+        bucket.tokens = bucket.tokens.min(bucket.rate as f64);
+        bucket.refilled_at = Instant::now();
+    }
+
+    // Largest read worth making at once, so a slow limit is spread over many small waits
+    // instead of a few long ones.
+    pub fn chunk_size(&self, max: usize) -> usize {
+        match self.bucket.lock().unwrap().rate {
+            0 => max,
+            rate => ((rate / 8) as usize).clamp(4096, max),
+        }
+    }
+
+    // Takes `bytes` from the bucket, then blocks until it is no longer overdrawn or `stop`
+    // is set.
+    pub fn consume(&self, bytes: usize, stop: &AtomicBool) {
+        let mut wait = self.overdraw(bytes);
+        while !wait.is_zero() && !stop.load(Ordering::Relaxed) {
+            let step = wait.min(MAX_WAIT);
+            std::thread::sleep(step);
+            wait -= step;
+        }
+    }
+
+    fn overdraw(&self, bytes: usize) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        if bucket.rate == 0 {
+            return Duration::ZERO;
+        }
+        let rate = bucket.rate as f64;
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate) - bytes as f64;
+        bucket.refilled_at = now;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
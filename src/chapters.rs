@@ -1,4 +1,6 @@
+use crate::ports::{HttpFetcher, HttpRequest, RequestPurpose};
 use serde::Deserialize;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Deserialize)]
 struct ChaptersJson {
@@ -31,8 +33,13 @@ pub struct Chapter {
     pub toc: bool,
 }
 
-pub async fn fetch_chapters(url: &str) -> anyhow::Result<Vec<Chapter>> {
-    let json: ChaptersJson = reqwest::get(url).await?.json().await?;
+pub async fn fetch_chapters(http: Arc<dyn HttpFetcher>, url: &str) -> anyhow::Result<Vec<Chapter>> {
+    let request = HttpRequest::get(RequestPurpose::Chapters, url);
+    let json: ChaptersJson = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+        let response = http.get(request)?.error_for_status()?;
+        Ok(serde_json::from_reader(response.body)?)
+    })
+    .await??;
     let chapters = json
         .chapters
         .into_iter()
@@ -18,12 +18,27 @@ pub enum AppCommand {
         podcast_id: i32,
         prefs: PodcastPreferences,
     },
+    // Folds one subscription's episodes and notes into another, then removes it.
+    MergePodcasts {
+        from_id: i32,
+        into_id: i32,
+    },
 
     // -- Episodes --------------------------------------------------------------
     DownloadEpisode(i32),
+    PauseDownload(i32),
+    CancelDownload(i32),
+    RetryDownload(i32),
     DeleteDownload(i32),
+    // Moves every download to where the current naming template puts it.
+    RenameDownloads,
     TogglePlayed(i32),
     CompleteEpisode(i32),
+    // Protected episodes are skipped by retention cleanup and quota eviction.
+    SetEpisodeProtected {
+        episode_id: i32,
+        protected: bool,
+    },
     SetEpisodeSpeedPreset {
         episode_id: i32,
         speed: Option<f32>,
@@ -73,9 +88,35 @@ pub enum AppCommand {
     SetSleepTimer(Option<u64>),
 
     // -- Settings -------------------------------------------------------------
-    SaveSettings(Settings),
+    SaveSettings(Box<Settings>),
     ApplyHotkeys(HotkeySettings),
 
     // -- Statistics -----------------------------------------------------------
     LoadListeningStats,
+
+    // -- Network audit --------------------------------------------------------
+    LoadNetworkAudit {
+        purpose: Option<crate::ports::RequestPurpose>,
+        host: String,
+    },
+    ClearNetworkAudit,
+
+    // -- Storage --------------------------------------------------------------
+    LoadStorageUsage,
+    // Played downloads of one podcast, or of all of them.
+    DeletePlayedDownloads(Option<i32>),
+    DeletePodcastDownloads(i32),
+    // Moves existing downloads after the download directory has changed.
+    MoveLibrary {
+        from: String,
+        to: String,
+    },
+    // Checks the download folder against the database.
+    ReconcileDownloads,
+    // Marks missing downloads as not downloaded even though the whole folder looks gone.
+    ClearMissingDownloads,
+    // Deletes the given files if they still belong to no episode.
+    DeleteUnknownFiles(Vec<std::path::PathBuf>),
+    // Empties the audio cache, in memory and on disk.
+    ClearAudioCache,
 }
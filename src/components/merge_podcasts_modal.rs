@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use crate::db::models::Podcast;
+use crate::design::components::*;
+use crate::design::spacing::*;
+use crate::design::tokens::ThemeTokens;
+use crate::design::typography::*;
+
+// Offered when a podcast's feed moves to a URL that is already subscribed.
+pub struct MergePodcastsModal {
+    // (moved, existing)
+    pending: Option<(Podcast, Podcast)>,
+    // Offers declined this session, so background syncs don't keep re-prompting.
+    dismissed: HashSet<(i32, i32)>,
+}
+
+impl MergePodcastsModal {
+    pub fn new() -> Self {
+        Self {
+            pending: None,
+            dismissed: HashSet::new(),
+        }
+    }
+
+    pub fn offer(&mut self, moved: Podcast, existing: Podcast) {
+        if self.pending.is_some() || self.dismissed.contains(&(moved.id, existing.id)) {
+            return;
+        }
+        self.pending = Some((moved, existing));
+    }
+
+    // Returns (from_id, into_id) when the user confirms the merge.
+    pub fn render(&mut self, ctx: &egui::Context, t: &ThemeTokens) -> Option<(i32, i32)> {
+        let (moved, existing) = self.pending.as_ref()?;
+        let mut result = None;
+        let mut close = false;
+
+        egui::Window::new("Merge Podcasts")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .frame(
+                egui::Frame::new()
+                    .fill(t.card_bg)
+                    .stroke(egui::Stroke::new(1.0, t.border))
+                    .corner_radius(rounding_lg())
+                    .inner_margin(egui::Margin::same(CARD_PADDING as i8)),
+            )
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.set_width(400.0);
+
+                    ui.label(text_page_title("Feed Moved", t));
+                    ui.add_space(SPACE_2);
+                    ui.label(text_body(
+                        format!(
+                            "'{}' has moved to a feed you already subscribe to as '{}'.",
+                            moved.title, existing.title
+                        ),
+                        t,
+                    ));
+                    ui.add_space(SPACE_1);
+                    ui.label(text_hint(
+                        "Merging moves its episodes, progress and notes into the existing \
+                         subscription and removes the old one.",
+                        t,
+                    ));
+
+                    ui.add_space(SPACE_3);
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if btn_primary(ui, "Merge", t).clicked() {
+                            result = Some((moved.id, existing.id));
+                            close = true;
+                        }
+
+                        ui.add_space(SPACE_2);
+
+                        if btn_secondary(ui, "Keep Both", t).clicked() {
+                            self.dismissed.insert((moved.id, existing.id));
+                            close = true;
+                        }
+                    });
+                });
+            });
+
+        if close {
+            self.pending = None;
+        }
+        result
+    }
+}
@@ -1,5 +1,6 @@
 pub mod add_podcast_modal;
 pub mod media_controls;
+pub mod merge_podcasts_modal;
 pub mod menu;
 pub mod notes_panel;
 pub mod podcast_card;
@@ -182,6 +182,13 @@ impl<'a> PodcastCard<'a> {
                         ),
                         t,
                     ));
+
+                    if !self.is_syncing
+                        && let Some(health) = self.podcast.sync_health()
+                    {
+                        ui.label(text_meta(truncate(&health, 34), t).color(t.error))
+                            .on_hover_text(health);
+                    }
                 });
             });
 
@@ -5,12 +5,18 @@ use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
 use dirs::data_local_dir;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 
 use crate::errors::DatabaseError;
 use crate::migrations::run_migrations;
 use crate::types::{HomeDensity, PodcastPreferences, QueueDisplayItem, QueueItem, Settings, ThemeMode, TrimSilenceMode};
-use models::{Bookmark, DownloadStatus, Episode, Podcast};
+use crate::ports::RequestPurpose;
+use models::{
+    Bookmark, DownloadStatus, DownloadedFile, Episode, LibraryMoveEntry, LibraryMoveState,
+    NetworkAuditEntry, Podcast,
+};
+
+const MAX_NETWORK_AUDIT_ENTRIES: i64 = 20_000;
 
 #[derive(Clone)]
 pub struct Database {
@@ -45,7 +51,13 @@ impl Database {
                 "SELECT p.id, p.url, p.title, p.description, p.image_url,
                         p.last_synced_at, p.created_at, p.updated_at,
                         p.speed_preset, p.auto_download, p.keep_episodes_count,
-                        p.skip_intro_seconds, p.skip_outro_seconds
+                        p.skip_intro_seconds, p.skip_outro_seconds,
+                        p.etag, p.last_modified,
+                        p.sync_interval_minutes, p.feed_ttl_minutes,
+                        p.feed_update_minutes, p.publish_interval_minutes,
+                        p.last_sync_attempt_at, p.sync_failure_count,
+                        p.sync_failing_since, p.sync_last_error,
+                        p.delete_played_after_days, p.delete_older_than_days, p.keep_unplayed
                  FROM podcasts p
                  ORDER BY p.title",
             )?;
@@ -67,6 +79,19 @@ impl Database {
                         keep_episodes_count: row.get(10)?,
                         skip_intro_seconds: row.get::<_, Option<i32>>(11)?.unwrap_or(0),
                         skip_outro_seconds: row.get::<_, Option<i32>>(12)?.unwrap_or(0),
+                        etag: row.get(13)?,
+                        last_modified: row.get(14)?,
+                        sync_interval_minutes: row.get(15)?,
+                        feed_ttl_minutes: row.get(16)?,
+                        feed_update_minutes: row.get(17)?,
+                        publish_interval_minutes: row.get(18)?,
+                        last_sync_attempt_at: row.get(19)?,
+                        sync_failure_count: row.get(20)?,
+                        sync_failing_since: row.get(21)?,
+                        sync_last_error: row.get(22)?,
+                        delete_played_after_days: row.get(23)?,
+                        delete_older_than_days: row.get(24)?,
+                        keep_unplayed: row.get::<_, Option<i32>>(25)?.map(|v| v != 0),
                     })
                 })?
                 .collect::<Result<Vec<_>, _>>()?;
@@ -85,7 +110,13 @@ impl Database {
                         p.last_synced_at, p.created_at, p.updated_at,
                         COUNT(e.id) as episode_count,
                         p.speed_preset, p.auto_download, p.keep_episodes_count,
-                        p.skip_intro_seconds, p.skip_outro_seconds
+                        p.skip_intro_seconds, p.skip_outro_seconds,
+                        p.etag, p.last_modified,
+                        p.sync_interval_minutes, p.feed_ttl_minutes,
+                        p.feed_update_minutes, p.publish_interval_minutes,
+                        p.last_sync_attempt_at, p.sync_failure_count,
+                        p.sync_failing_since, p.sync_last_error,
+                        p.delete_played_after_days, p.delete_older_than_days, p.keep_unplayed
                  FROM podcasts p
                  LEFT JOIN episodes e ON e.podcast_id = p.id
                  WHERE p.id = ?
@@ -108,6 +139,19 @@ impl Database {
                     keep_episodes_count: row.get(11)?,
                     skip_intro_seconds: row.get::<_, Option<i32>>(12)?.unwrap_or(0),
                     skip_outro_seconds: row.get::<_, Option<i32>>(13)?.unwrap_or(0),
+                    etag: row.get(14)?,
+                    last_modified: row.get(15)?,
+                    sync_interval_minutes: row.get(16)?,
+                    feed_ttl_minutes: row.get(17)?,
+                    feed_update_minutes: row.get(18)?,
+                    publish_interval_minutes: row.get(19)?,
+                    last_sync_attempt_at: row.get(20)?,
+                    sync_failure_count: row.get(21)?,
+                    sync_failing_since: row.get(22)?,
+                    sync_last_error: row.get(23)?,
+                    delete_played_after_days: row.get(24)?,
+                    delete_older_than_days: row.get(25)?,
+                    keep_unplayed: row.get::<_, Option<i32>>(26)?.map(|v| v != 0),
                 })
             })?;
 
@@ -116,14 +160,27 @@ impl Database {
         .await?
     }
 
+    pub async fn get_podcast_id_by_url(&self, url: String) -> anyhow::Result<Option<i32>> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            Ok(conn
+                .query_row("SELECT id FROM podcasts WHERE url = ?", [url], |row| row.get(0))
+                .optional()?)
+        })
+        .await?
+    }
+
     pub async fn insert_podcast(&self, podcast: Podcast) -> anyhow::Result<Podcast> {
         let conn = self.connection.clone();
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
             conn.execute(
                 "INSERT INTO podcasts
-                    (url, title, description, image_url, last_synced_at, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    (url, title, description, image_url, last_synced_at, created_at, updated_at,
+                     etag, last_modified,
+                     feed_ttl_minutes, feed_update_minutes, publish_interval_minutes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
                 params![
                     podcast.url,
                     podcast.title,
@@ -132,6 +189,11 @@ impl Database {
                     podcast.last_synced_at,
                     podcast.created_at,
                     podcast.updated_at,
+                    podcast.etag,
+                    podcast.last_modified,
+                    podcast.feed_ttl_minutes,
+                    podcast.feed_update_minutes,
+                    podcast.publish_interval_minutes,
                 ],
             )?;
 
@@ -144,13 +206,20 @@ impl Database {
         .await?
     }
 
+    // Marks a successful sync, which also clears any recorded failures.
     pub async fn update_podcast_synced_at(&self, podcast_id: i32) -> anyhow::Result<()> {
         let conn = self.connection.clone();
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
             let now = chrono::Utc::now().timestamp();
             conn.execute(
-                "UPDATE podcasts SET last_synced_at = ?1, updated_at = ?1 WHERE id = ?2",
+                "UPDATE podcasts SET
+                    last_synced_at = ?1,
+                    updated_at = ?1,
+                    sync_failure_count = 0,
+                    sync_failing_since = NULL,
+                    sync_last_error = NULL
+                 WHERE id = ?2",
                 params![now, podcast_id],
             )?;
             Ok(())
@@ -158,6 +227,129 @@ impl Database {
         .await?
     }
 
+    pub async fn record_sync_attempt(&self, podcast_id: i32) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let now = chrono::Utc::now().timestamp();
+            conn.execute(
+                "UPDATE podcasts SET last_sync_attempt_at = ?1 WHERE id = ?2",
+                params![now, podcast_id],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
+    // Returns the number of consecutive failures, including this one.
+    pub async fn record_sync_failure(&self, podcast_id: i32, error: String) -> anyhow::Result<i32> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let now = chrono::Utc::now().timestamp();
+            Ok(conn.query_row(
+                "UPDATE podcasts SET
+                    sync_failure_count = sync_failure_count + 1,
+                    sync_failing_since = COALESCE(sync_failing_since, ?1),
+                    sync_last_error = ?2
+                 WHERE id = ?3
+                 RETURNING sync_failure_count",
+                params![now, error, podcast_id],
+                |row| row.get(0),
+            )?)
+        })
+        .await?
+    }
+
+    // Stores the ETag / Last-Modified validators returned by the feed host.
+    pub async fn update_podcast_validators(
+        &self,
+        podcast_id: i32,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            conn.execute(
+                "UPDATE podcasts SET etag = ?1, last_modified = ?2 WHERE id = ?3",
+                params![etag, last_modified, podcast_id],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
+    // Applies the feed's current title, description and artwork. Empty values from the feed
+    // are ignored. Returns true if the row changed.
+    pub async fn update_podcast_metadata(
+        &self,
+        podcast_id: i32,
+        title: String,
+        description: String,
+        image_url: String,
+    ) -> anyhow::Result<bool> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let now = chrono::Utc::now().timestamp();
+            let changed = conn.execute(
+                "UPDATE podcasts SET
+                    title = CASE WHEN ?1 != '' THEN ?1 ELSE title END,
+                    description = CASE WHEN ?2 != '' THEN ?2 ELSE description END,
+                    image_url = CASE WHEN ?3 != '' THEN ?3 ELSE image_url END,
+                    updated_at = ?4
+                 WHERE id = ?5
+                   AND ((?1 != '' AND title IS NOT ?1)
+                        OR (?2 != '' AND description IS NOT ?2)
+                        OR (?3 != '' AND image_url IS NOT ?3))",
+                params![title, description, image_url, now, podcast_id],
+            )?;
+            Ok(changed > 0)
+        })
+        .await?
+    }
+
+    // Stores the inputs the sync scheduler uses to pick this podcast's interval.
+    pub async fn update_podcast_sync_hints(
+        &self,
+        podcast_id: i32,
+        feed_ttl_minutes: Option<i32>,
+        feed_update_minutes: Option<i32>,
+        publish_interval_minutes: Option<i32>,
+    ) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            conn.execute(
+                "UPDATE podcasts
+                 SET feed_ttl_minutes = ?1, feed_update_minutes = ?2, publish_interval_minutes = ?3
+                 WHERE id = ?4",
+                params![feed_ttl_minutes, feed_update_minutes, publish_interval_minutes, podcast_id],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
+    // Re-points a podcast at its new feed URL. The stored validators belonged to the old
+    // URL, so they are dropped and the next sync fetches unconditionally.
+    pub async fn update_podcast_url(&self, podcast_id: i32, url: String) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let now = chrono::Utc::now().timestamp();
+            conn.execute(
+                "UPDATE podcasts
+                 SET url = ?1, etag = NULL, last_modified = NULL, updated_at = ?2
+                 WHERE id = ?3",
+                params![url, now, podcast_id],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
     pub async fn update_podcast_preferences(
         &self,
         podcast_id: i32,
@@ -174,14 +366,22 @@ impl Database {
                     keep_episodes_count = ?3,
                     skip_intro_seconds = ?4,
                     skip_outro_seconds = ?5,
-                    updated_at = ?6
-                 WHERE id = ?7",
+                    sync_interval_minutes = ?6,
+                    delete_played_after_days = ?7,
+                    delete_older_than_days = ?8,
+                    keep_unplayed = ?9,
+                    updated_at = ?10
+                 WHERE id = ?11",
                 params![
                     prefs.speed_preset,
                     prefs.auto_download.map(|b| b as i32),
                     prefs.keep_episodes_count,
                     prefs.skip_intro_seconds,
                     prefs.skip_outro_seconds,
+                    prefs.sync_interval_minutes,
+                    prefs.delete_played_after_days,
+                    prefs.delete_older_than_days,
+                    prefs.keep_unplayed.map(|b| b as i32),
                     now,
                     podcast_id,
                 ],
@@ -201,6 +401,143 @@ impl Database {
         .await?
     }
 
+    // Folds `from_id` into `into_id` and deletes `from_id`. Episodes already present in the
+    // target (same GUID or URL) keep the furthest playback state and take over the source's
+    // notes and queue entries; the rest are moved across. Returns downloaded file paths that
+    // are no longer referenced by any episode.
+    pub async fn merge_podcasts(&self, from_id: i32, into_id: i32) -> anyhow::Result<Vec<String>> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let tx = conn.transaction()?;
+            let now = chrono::Utc::now().timestamp();
+            let mut orphaned_files = Vec::new();
+
+            let sources = {
+                let mut stmt = tx.prepare(
+                    "SELECT id, guid, url, is_played, position_seconds, download_status,
+                            downloaded_path, speed_preset, total_listen_seconds
+                     FROM episodes WHERE podcast_id = ?",
+                )?;
+                stmt.query_map([from_id], |row| {
+                    Ok((
+                        row.get::<_, i32>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, i32>(3)?,
+                        row.get::<_, f64>(4)?,
+                        row.get::<_, Option<String>>(5)?,
+                        row.get::<_, Option<String>>(6)?,
+                        row.get::<_, Option<f32>>(7)?,
+                        row.get::<_, i64>(8)?,
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()?
+            };
+
+            for (id, guid, url, is_played, position, status, path, speed, listened) in sources {
+                let target: Option<(i32, Option<String>)> = tx
+                    .query_row(
+                        "SELECT id, download_status FROM episodes
+                         WHERE podcast_id = ?1 AND ((?2 IS NOT NULL AND guid = ?2) OR url = ?3)
+                         ORDER BY (guid IS ?2) DESC
+                         LIMIT 1",
+                        params![into_id, guid, url],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+
+                let Some((target_id, target_status)) = target else {
+                    tx.execute(
+                        "UPDATE episodes SET podcast_id = ?1, updated_at = ?2 WHERE id = ?3",
+                        params![into_id, now, id],
+                    )?;
+                    continue;
+                };
+
+                // Keep whichever copy has the download; drop the other file.
+                let source_downloaded = status.as_deref() == Some(DownloadStatus::Downloaded.as_str());
+                let target_downloaded =
+                    target_status.as_deref() == Some(DownloadStatus::Downloaded.as_str());
+                let adopt_download = source_downloaded && !target_downloaded;
+                if source_downloaded && target_downloaded && let Some(path) = path.clone() {
+                    orphaned_files.push(path);
+                }
+
+                tx.execute(
+                    "UPDATE episodes SET
+                        is_played = MAX(is_played, ?1),
+                        played_at = COALESCE(played_at,
+                            (SELECT played_at FROM episodes WHERE id = ?10)),
+                        is_protected = MAX(is_protected,
+                            (SELECT is_protected FROM episodes WHERE id = ?10)),
+                        position_seconds = MAX(position_seconds, ?2),
+                        speed_preset = COALESCE(speed_preset, ?3),
+                        total_listen_seconds = total_listen_seconds + ?4,
+                        download_status = CASE WHEN ?5 THEN ?6 ELSE download_status END,
+                        downloaded_path = CASE WHEN ?5 THEN ?7 ELSE downloaded_path END,
+                        downloaded_at = CASE WHEN ?5
+                            THEN (SELECT downloaded_at FROM episodes WHERE id = ?10)
+                            ELSE downloaded_at END,
+                        updated_at = ?8
+                     WHERE id = ?9",
+                    params![
+                        is_played,
+                        position,
+                        speed,
+                        listened,
+                        adopt_download,
+                        status,
+                        path,
+                        now,
+                        target_id,
+                        id
+                    ],
+                )?;
+                tx.execute(
+                    "UPDATE bookmarks SET episode_id = ?1, podcast_id = ?2 WHERE episode_id = ?3",
+                    params![target_id, into_id, id],
+                )?;
+                // Avoid queueing the same episode twice.
+                tx.execute(
+                    "DELETE FROM queue
+                     WHERE episode_id = ?1
+                       AND EXISTS (SELECT 1 FROM queue WHERE episode_id = ?2)",
+                    params![id, target_id],
+                )?;
+                tx.execute(
+                    "UPDATE queue SET episode_id = ?1 WHERE episode_id = ?2",
+                    params![target_id, id],
+                )?;
+                tx.execute("DELETE FROM episodes WHERE id = ?", [id])?;
+            }
+
+            // Podcast-level notes.
+            tx.execute(
+                "UPDATE bookmarks SET podcast_id = ?1 WHERE podcast_id = ?2",
+                params![into_id, from_id],
+            )?;
+            tx.execute("DELETE FROM podcasts WHERE id = ?", [from_id])?;
+
+            // Both copies may have been saved under the same name, and so share one file.
+            let mut unreferenced = Vec::new();
+            for path in orphaned_files {
+                let in_use: bool = tx.query_row(
+                    "SELECT EXISTS (SELECT 1 FROM episodes WHERE downloaded_path = ?)",
+                    [&path],
+                    |row| row.get(0),
+                )?;
+                if !in_use && !unreferenced.contains(&path) {
+                    unreferenced.push(path);
+                }
+            }
+
+            tx.commit()?;
+            Ok(unreferenced)
+        })
+        .await?
+    }
+
     // Episodes
 
     pub async fn get_episodes(&self, podcast_id: i32) -> anyhow::Result<Vec<Episode>> {
@@ -212,7 +549,8 @@ impl Database {
                         publish_date, is_played, duration, position_seconds,
                         created_at, updated_at,
                         download_status, downloaded_path, speed_preset, chapters_url,
-                        total_listen_seconds
+                        total_listen_seconds, guid, season, episode_number,
+                        is_protected, played_at, downloaded_at
                  FROM episodes
                  WHERE podcast_id = ?
                  ORDER BY publish_date DESC",
@@ -223,6 +561,7 @@ impl Database {
                     Ok(Episode {
                         id: row.get(0)?,
                         podcast_id: row.get(1)?,
+                        guid: row.get(17)?,
                         title: row.get(2)?,
                         description: row.get(3)?,
                         url: row.get(4)?,
@@ -241,6 +580,11 @@ impl Database {
                         speed_preset: row.get(14)?,
                         chapters_url: row.get(15)?,
                         total_listen_seconds: row.get(16)?,
+                        season: row.get(18)?,
+                        episode_number: row.get(19)?,
+                        is_protected: row.get::<_, i32>(20)? != 0,
+                        played_at: row.get(21)?,
+                        downloaded_at: row.get(22)?,
                     })
                 })?
                 .collect::<Result<Vec<_>, _>>()?;
@@ -259,7 +603,8 @@ impl Database {
                         publish_date, is_played, duration, position_seconds,
                         created_at, updated_at,
                         download_status, downloaded_path, speed_preset, chapters_url,
-                        total_listen_seconds
+                        total_listen_seconds, guid, season, episode_number,
+                        is_protected, played_at, downloaded_at
                  FROM episodes WHERE id = ?",
             )?;
 
@@ -267,6 +612,7 @@ impl Database {
                 Ok(Episode {
                     id: row.get(0)?,
                     podcast_id: row.get(1)?,
+                    guid: row.get(17)?,
                     title: row.get(2)?,
                     description: row.get(3)?,
                     url: row.get(4)?,
@@ -285,6 +631,11 @@ impl Database {
                     speed_preset: row.get(14)?,
                     chapters_url: row.get(15)?,
                     total_listen_seconds: row.get(16)?,
+                    season: row.get(18)?,
+                    episode_number: row.get(19)?,
+                    is_protected: row.get::<_, i32>(20)? != 0,
+                    played_at: row.get(21)?,
+                    downloaded_at: row.get(22)?,
                 })
             })?;
 
@@ -294,6 +645,12 @@ impl Database {
     }
 
     // Returns the IDs of newly inserted episodes (episodes that didn't exist before).
+    //
+    // Existing rows are matched by GUID first, falling back to the enclosure URL for items
+    // without one and for rows that predate GUID tracking. A URL match adopts the feed's
+    // GUID so later enclosure URL changes (CDN moves, tracking prefixes) don't create
+    // duplicates. Matched rows take the feed's current metadata; playback and download
+    // state are left alone.
     pub async fn insert_episodes(&self, episodes: Vec<Episode>) -> anyhow::Result<Vec<i32>> {
         let conn = self.connection.clone();
         tokio::task::spawn_blocking(move || {
@@ -302,34 +659,113 @@ impl Database {
             let mut new_ids = Vec::new();
 
             for ep in &episodes {
+                let by_guid: Option<i32> = match &ep.guid {
+                    Some(guid) => tx
+                        .query_row(
+                            "SELECT id FROM episodes WHERE podcast_id = ?1 AND guid = ?2",
+                            params![ep.podcast_id, guid],
+                            |row| row.get(0),
+                        )
+                        .optional()?,
+                    None => None,
+                };
+
+                // A row whose GUID was confirmed by the feed belongs to a different item,
+                // even if the publisher reused its enclosure URL.
+                let existing_id = match by_guid {
+                    Some(id) => Some(id),
+                    None => tx
+                        .query_row(
+                            "SELECT id FROM episodes
+                             WHERE podcast_id = ?1 AND url = ?2
+                               AND (?3 IS NULL OR guid IS NULL OR guid = url)",
+                            params![ep.podcast_id, ep.url, ep.guid],
+                            |row| row.get(0),
+                        )
+                        .optional()?,
+                };
+
+                // Both lookups above ran inside this transaction, so a GUID that still
+                // collides here is a real error rather than something to skip silently.
+                let Some(id) = existing_id else {
+                    tx.execute(
+                        "INSERT INTO episodes
+                            (podcast_id, guid, title, description, url, audio_type,
+                             publish_date, is_played, duration, position_seconds,
+                             created_at, updated_at, chapters_url, season, episode_number)
+                         VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,0.0,?10,?11,?12,?13,?14)",
+                        params![
+                            ep.podcast_id,
+                            ep.guid,
+                            ep.title,
+                            ep.description,
+                            ep.url,
+                            ep.audio_type,
+                            ep.publish_date,
+                            ep.is_played as i32,
+                            ep.duration,
+                            ep.created_at,
+                            ep.updated_at,
+                            ep.chapters_url,
+                            ep.season,
+                            ep.episode_number,
+                        ],
+                    )?;
+                    new_ids.push(tx.last_insert_rowid() as i32);
+                    continue;
+                };
+
+                // No other row of the show has this GUID, or the lookup would have found it.
+                if ep.guid.is_some() {
+                    tx.execute(
+                        "UPDATE episodes SET guid = ?1 WHERE id = ?2 AND guid IS NOT ?1",
+                        params![ep.guid, id],
+                    )?;
+                }
+                // Only touch the row (and updated_at) when something actually changed. A
+                // zero duration or publish date means the feed omitted it, so keep ours.
                 tx.execute(
-                    "INSERT OR IGNORE INTO episodes
-                        (podcast_id, title, description, url, audio_type,
-                         publish_date, is_played, duration, position_seconds,
-                         created_at, updated_at, chapters_url)
-                     VALUES (?1,?2,?3,?4,?5,?6,?7,?8,0.0,?9,?10,?11)",
+                    "UPDATE episodes SET
+                        title = ?1,
+                        description = ?2,
+                        duration = CASE WHEN ?3 > 0 THEN ?3 ELSE duration END,
+                        publish_date = CASE WHEN ?4 > 0 THEN ?4 ELSE publish_date END,
+                        updated_at = ?5
+                     WHERE id = ?6
+                       AND (title IS NOT ?1
+                            OR description IS NOT ?2
+                            OR (?3 > 0 AND duration IS NOT ?3)
+                            OR (?4 > 0 AND publish_date IS NOT ?4))",
                     params![
-                        ep.podcast_id,
                         ep.title,
                         ep.description,
-                        ep.url,
-                        ep.audio_type,
-                        ep.publish_date,
-                        ep.is_played as i32,
                         ep.duration,
-                        ep.created_at,
+                        ep.publish_date,
                         ep.updated_at,
-                        ep.chapters_url,
+                        id,
                     ],
                 )?;
-                if tx.changes() > 0 {
-                    new_ids.push(tx.last_insert_rowid() as i32);
-                } else if ep.chapters_url.is_some() {
+                tx.execute(
+                    "UPDATE episodes SET url = ?1, audio_type = ?2, updated_at = ?3
+                     WHERE id = ?4 AND (url IS NOT ?1 OR audio_type IS NOT ?2)",
+                    params![ep.url, ep.audio_type, ep.updated_at, id],
+                )?;
+                if ep.chapters_url.is_some() {
                     // Episode already exists — backfill chapters_url if it wasn't set before.
                     tx.execute(
-                        "UPDATE episodes SET chapters_url = ?1
-                         WHERE podcast_id = ?2 AND url = ?3 AND chapters_url IS NULL",
-                        params![ep.chapters_url, ep.podcast_id, ep.url],
+                        "UPDATE episodes SET chapters_url = ?1 WHERE id = ?2 AND chapters_url IS NULL",
+                        params![ep.chapters_url, id],
+                    )?;
+                }
+                if ep.season.is_some() || ep.episode_number.is_some() {
+                    tx.execute(
+                        "UPDATE episodes SET
+                            season = COALESCE(?1, season),
+                            episode_number = COALESCE(?2, episode_number)
+                         WHERE id = ?3
+                           AND (season IS NOT COALESCE(?1, season)
+                                OR episode_number IS NOT COALESCE(?2, episode_number))",
+                        params![ep.season, ep.episode_number, id],
                     )?;
                 }
             }
@@ -350,7 +786,11 @@ impl Database {
             let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
             let now = chrono::Utc::now().timestamp();
             conn.execute(
-                "UPDATE episodes SET is_played = ?, updated_at = ? WHERE id = ?",
+                "UPDATE episodes SET
+                    is_played = ?1,
+                    played_at = CASE WHEN ?1 THEN ?2 END,
+                    updated_at = ?2
+                 WHERE id = ?3",
                 params![is_played as i32, now, episode_id],
             )?;
             Ok(())
@@ -386,8 +826,13 @@ impl Database {
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
             let now = chrono::Utc::now().timestamp();
+            // Moving or relinking a file keeps its original download time.
             conn.execute(
-                "UPDATE episodes SET download_status = ?1, downloaded_path = ?2, updated_at = ?3 WHERE id = ?4",
+                "UPDATE episodes SET download_status = ?1, downloaded_path = ?2,
+                    downloaded_at = CASE WHEN ?1 = 'downloaded' THEN COALESCE(
+                        CASE WHEN download_status = 'downloaded' THEN downloaded_at END, ?3) END,
+                    updated_at = ?3
+                 WHERE id = ?4",
                 params![status.as_str(), path, now, episode_id],
             )?;
             Ok(())
@@ -395,6 +840,24 @@ impl Database {
         .await?
     }
 
+    pub async fn update_episode_protected(
+        &self,
+        episode_id: i32,
+        protected: bool,
+    ) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let now = chrono::Utc::now().timestamp();
+            conn.execute(
+                "UPDATE episodes SET is_protected = ?1, updated_at = ?2 WHERE id = ?3",
+                params![protected as i32, now, episode_id],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
     pub async fn update_episode_speed_preset(
         &self,
         episode_id: i32,
@@ -420,7 +883,7 @@ impl Database {
             let now = chrono::Utc::now().timestamp();
             conn.execute(
                 "UPDATE episodes
-                 SET is_played = 1, position_seconds = 0.0, updated_at = ?1
+                 SET is_played = 1, played_at = ?1, position_seconds = 0.0, updated_at = ?1
                  WHERE id = ?2",
                 params![now, episode_id],
             )?;
@@ -442,7 +905,8 @@ impl Database {
                         publish_date, is_played, duration, position_seconds,
                         created_at, updated_at,
                         download_status, downloaded_path, speed_preset, chapters_url,
-                        total_listen_seconds
+                        total_listen_seconds, guid, season, episode_number,
+                        is_protected, played_at, downloaded_at
                  FROM episodes
                  WHERE podcast_id = ? AND download_status = 'downloaded'
                  ORDER BY publish_date DESC",
@@ -453,6 +917,7 @@ impl Database {
                     Ok(Episode {
                         id: row.get(0)?,
                         podcast_id: row.get(1)?,
+                        guid: row.get(17)?,
                         title: row.get(2)?,
                         description: row.get(3)?,
                         url: row.get(4)?,
@@ -468,6 +933,11 @@ impl Database {
                         speed_preset: row.get(14)?,
                         chapters_url: row.get(15)?,
                         total_listen_seconds: row.get(16)?,
+                        season: row.get(18)?,
+                        episode_number: row.get(19)?,
+                        is_protected: row.get::<_, i32>(20)? != 0,
+                        played_at: row.get(21)?,
+                        downloaded_at: row.get(22)?,
                     })
                 })?
                 .collect::<Result<Vec<_>, _>>()?;
@@ -477,6 +947,299 @@ impl Database {
         .await?
     }
 
+    // Download queue
+
+    // Adds an episode to the download queue, or puts a paused/failed entry back in line
+    // with a fresh retry budget. An entry the user asked for stays manual.
+    pub async fn enqueue_download(&self, episode_id: i32, is_auto: bool) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let now = chrono::Utc::now().timestamp();
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT INTO download_queue (episode_id, state, is_auto, created_at)
+                 VALUES (?1, 'queued', ?2, ?3)
+                 ON CONFLICT(episode_id) DO UPDATE SET
+                    state = 'queued', attempts = 0, next_attempt_at = 0, last_error = NULL,
+                    is_auto = MIN(is_auto, excluded.is_auto)",
+                params![episode_id, is_auto as i32, now],
+            )?;
+            tx.execute(
+                "UPDATE episodes SET download_status = ?1, updated_at = ?2 WHERE id = ?3",
+                params![DownloadStatus::Queued.as_str(), now, episode_id],
+            )?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await?
+    }
+
+    // Queued entries whose retry time has come, oldest first, as (episode_id, is_auto).
+    // Automatic downloads are left out unless `include_auto` is set.
+    pub async fn get_due_downloads(
+        &self,
+        now: i64,
+        limit: i64,
+        include_auto: bool,
+    ) -> anyhow::Result<Vec<(i32, bool)>> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let mut stmt = conn.prepare(
+                "SELECT episode_id, is_auto FROM download_queue
+                 WHERE state = 'queued' AND next_attempt_at <= ?1 AND (?3 OR is_auto = 0)
+                 ORDER BY created_at, episode_id
+                 LIMIT ?2",
+            )?;
+            let due = stmt
+                .query_map(params![now, limit, include_auto], |row| {
+                    Ok((row.get(0)?, row.get::<_, i32>(1)? != 0))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(due)
+        })
+        .await?
+    }
+
+    // Pauses a queued or running download. Returns false if it is no longer queued, e.g.
+    // because it has just finished.
+    pub async fn pause_queued_download(&self, episode_id: i32) -> anyhow::Result<bool> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let now = chrono::Utc::now().timestamp();
+            let tx = conn.transaction()?;
+            let paused = tx.execute(
+                "UPDATE download_queue SET state = 'paused' WHERE episode_id = ?1",
+                [episode_id],
+            )? > 0;
+            if paused {
+                tx.execute(
+                    "UPDATE episodes SET download_status = ?1, updated_at = ?2 WHERE id = ?3",
+                    params![DownloadStatus::Paused.as_str(), now, episode_id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(paused)
+        })
+        .await?
+    }
+
+    pub async fn remove_queued_download(&self, episode_id: i32) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            conn.execute("DELETE FROM download_queue WHERE episode_id = ?1", [episode_id])?;
+            Ok(())
+        })
+        .await?
+    }
+
+    // Episodes of a podcast that are queued, running or paused in the download queue.
+    pub async fn get_queued_download_ids(&self, podcast_id: i32) -> anyhow::Result<Vec<i32>> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let mut stmt = conn.prepare(
+                "SELECT q.episode_id FROM download_queue q
+                 JOIN episodes e ON e.id = q.episode_id
+                 WHERE e.podcast_id = ?1",
+            )?;
+            let ids = stmt
+                .query_map([podcast_id], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(ids)
+        })
+        .await?
+    }
+
+    // Counts a failed attempt and schedules the next one at `retry_at(attempts)`.
+    // Returns how many attempts have failed so far.
+    pub async fn record_download_failure(
+        &self,
+        episode_id: i32,
+        error: String,
+        retry_at: fn(i32) -> i64,
+    ) -> anyhow::Result<i32> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let attempts = conn.query_row(
+                "UPDATE download_queue
+                 SET attempts = attempts + 1, last_error = ?1
+                 WHERE episode_id = ?2
+                 RETURNING attempts",
+                params![error, episode_id],
+                |row| row.get(0),
+            )?;
+            conn.execute(
+                "UPDATE download_queue SET next_attempt_at = ?1 WHERE episode_id = ?2",
+                params![retry_at(attempts), episode_id],
+            )?;
+            Ok(attempts)
+        })
+        .await?
+    }
+
+    // Brings episode statuses back in line with the queue after a restart: anything that was
+    // mid-download when the app closed is queued again, and statuses left over from entries
+    // that no longer exist are cleared.
+    pub async fn restore_download_queue(&self) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            conn.execute_batch(
+                "UPDATE episodes SET download_status = 'queued'
+                 WHERE download_status IN ('downloading', 'paused', 'not_downloaded')
+                   AND id IN (SELECT episode_id FROM download_queue WHERE state = 'queued');
+                 UPDATE episodes SET download_status = 'paused'
+                 WHERE id IN (SELECT episode_id FROM download_queue WHERE state = 'paused');
+                 UPDATE episodes SET download_status = 'not_downloaded'
+                 WHERE download_status IN ('queued', 'downloading', 'paused')
+                   AND id NOT IN (SELECT episode_id FROM download_queue);",
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
+    // Library move
+
+    // Records the files to move. Fails if another move hasn't finished yet.
+    pub async fn start_library_move(&self, entries: Vec<LibraryMoveEntry>) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let tx = conn.transaction()?;
+            let pending: i64 = tx.query_row("SELECT COUNT(*) FROM library_move", [], |row| row.get(0))?;
+            if pending > 0 {
+                return Err(anyhow!("Another move of the downloads has not finished"));
+            }
+            for entry in &entries {
+                tx.execute(
+                    "INSERT INTO library_move (episode_id, from_root, from_path, to_path, state)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        entry.episode_id,
+                        entry.from_root,
+                        entry.from_path,
+                        entry.to_path,
+                        entry.state.as_str()
+                    ],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await?
+    }
+
+    pub async fn get_library_move(&self) -> anyhow::Result<Vec<LibraryMoveEntry>> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let mut stmt = conn.prepare(
+                "SELECT episode_id, from_root, from_path, to_path, state
+                 FROM library_move
+                 ORDER BY episode_id",
+            )?;
+            let entries = stmt
+                .query_map([], |row| {
+                    Ok(LibraryMoveEntry {
+                        episode_id: row.get(0)?,
+                        from_root: row.get(1)?,
+                        from_path: row.get(2)?,
+                        to_path: row.get(3)?,
+                        state: LibraryMoveState::from_str(&row.get::<_, String>(4)?),
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(entries)
+        })
+        .await?
+    }
+
+    pub async fn mark_library_move_copied(
+        &self,
+        episode_id: i32,
+        to_path: String,
+    ) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            conn.execute(
+                "UPDATE library_move SET to_path = ?1, state = 'copied' WHERE episode_id = ?2",
+                params![to_path, episode_id],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
+    // Points every copied episode at its new file in one transaction, and drops entries
+    // that were never copied. Returns the copied entries whose episode no longer uses the
+    // old file (deleted or moved meanwhile); their copies are left for the caller to remove.
+    pub async fn commit_library_move(&self) -> anyhow::Result<Vec<LibraryMoveEntry>> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let tx = conn.transaction()?;
+            let now = chrono::Utc::now().timestamp();
+            let copied = {
+                let mut stmt = tx.prepare(
+                    "SELECT episode_id, from_root, from_path, to_path
+                     FROM library_move WHERE state = 'copied'",
+                )?;
+                stmt.query_map([], |row| {
+                    Ok(LibraryMoveEntry {
+                        episode_id: row.get(0)?,
+                        from_root: row.get(1)?,
+                        from_path: row.get(2)?,
+                        to_path: row.get(3)?,
+                        state: LibraryMoveState::Copied,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?
+            };
+
+            let mut stale = Vec::new();
+            for entry in copied {
+                let updated = tx.execute(
+                    "UPDATE episodes SET downloaded_path = ?1, updated_at = ?2
+                     WHERE id = ?3 AND download_status = 'downloaded' AND downloaded_path = ?4",
+                    params![entry.to_path, now, entry.episode_id, entry.from_path],
+                )?;
+                if updated > 0 {
+                    tx.execute(
+                        "UPDATE library_move SET state = 'committed' WHERE episode_id = ?1",
+                        [entry.episode_id],
+                    )?;
+                } else {
+                    tx.execute(
+                        "DELETE FROM library_move WHERE episode_id = ?1",
+                        [entry.episode_id],
+                    )?;
+                    stale.push(entry);
+                }
+            }
+            tx.execute("DELETE FROM library_move WHERE state = 'pending'", [])?;
+            tx.commit()?;
+            Ok(stale)
+        })
+        .await?
+    }
+
+    pub async fn finish_library_move_entry(&self, episode_id: i32) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            conn.execute("DELETE FROM library_move WHERE episode_id = ?1", [episode_id])?;
+            Ok(())
+        })
+        .await?
+    }
+
     // Bookmarks
 
     pub async fn get_bookmarks_for_episode(
@@ -714,7 +1477,18 @@ impl Database {
                     "sync_interval_minutes" => {
                         settings.sync_interval_minutes = row.1.parse().unwrap_or(30)
                     }
+                    "sync_concurrency" => {
+                        settings.sync_concurrency = row.1.parse().unwrap_or(4)
+                    }
                     "auto_play_next" => settings.auto_play_next = row.1 == "true",
+                    "prefetch_minutes" => settings.prefetch_minutes = row.1.parse().unwrap_or(5),
+                    "prefetch_max_mb" => settings.prefetch_max_mb = row.1.parse().unwrap_or(200),
+                    "audio_cache_memory_mb" => {
+                        settings.audio_cache_memory_mb = row.1.parse().unwrap_or(256)
+                    }
+                    "audio_cache_disk_mb" => {
+                        settings.audio_cache_disk_mb = row.1.parse().unwrap_or(2048)
+                    }
                     "download_directory" => settings.download_directory = row.1,
                     "home_density" => {
                         settings.home_density = match row.1.as_str() {
@@ -723,6 +1497,7 @@ impl Database {
                         }
                     }
                     "default_speed" => settings.default_speed = row.1.parse().unwrap_or(1.0),
+                    "preserve_pitch" => settings.preserve_pitch = row.1 == "true",
                     "trim_silence_mode" => {
                         settings.trim_silence_mode = match row.1.as_str() {
                             "smart_speed" => TrimSilenceMode::SmartSpeed,
@@ -733,9 +1508,37 @@ impl Database {
                     "auto_download_new_episodes" => {
                         settings.auto_download_new_episodes = row.1 == "true"
                     }
+                    "auto_download_window" => settings.auto_download_window = row.1 == "true",
+                    "auto_download_window_start" => {
+                        settings.auto_download_window_start = row.1.parse().unwrap_or(60)
+                    }
+                    "auto_download_window_end" => {
+                        settings.auto_download_window_end = row.1.parse().unwrap_or(6 * 60)
+                    }
+                    "download_bandwidth_kbps" => {
+                        settings.download_bandwidth_kbps = row.1.parse().unwrap_or(0)
+                    }
+                    "limit_manual_downloads" => settings.limit_manual_downloads = row.1 == "true",
                     "global_keep_episodes_count" => {
                         settings.global_keep_episodes_count = row.1.parse().unwrap_or(0)
                     }
+                    "retention_played_after_days" => {
+                        settings.retention_played_after_days = row.1.parse().unwrap_or(0)
+                    }
+                    "retention_older_than_days" => {
+                        settings.retention_older_than_days = row.1.parse().unwrap_or(0)
+                    }
+                    "retention_keep_unplayed" => settings.retention_keep_unplayed = row.1 == "true",
+                    "download_concurrency" => {
+                        settings.download_concurrency = row.1.parse().unwrap_or(2)
+                    }
+                    "download_name_template" => settings.download_name_template = row.1,
+                    "write_download_tags" => settings.write_download_tags = row.1 == "true",
+                    "download_quota_gb" => {
+                        settings.download_quota_gb = row.1.parse().unwrap_or(0)
+                    }
+                    "strip_tracking_prefixes" => settings.strip_tracking_prefixes = row.1 == "true",
+                    "tracking_prefix_rules" => settings.tracking_prefix_rules = row.1,
                     "hotkey_play_pause" => settings.hotkeys.play_pause = row.1,
                     "hotkey_next" => settings.hotkeys.next = row.1,
                     "hotkey_prev" => settings.hotkeys.prev = row.1,
@@ -751,6 +1554,15 @@ impl Database {
                             _ => ThemeMode::Dark,
                         }
                     }
+                    "http_proxy" => settings.http_proxy = row.1,
+                    "http_user_agent" => settings.http_user_agent = row.1,
+                    "http_connect_timeout_secs" => {
+                        settings.http_connect_timeout_secs = row.1.parse().unwrap_or(15)
+                    }
+                    "http_read_timeout_secs" => {
+                        settings.http_read_timeout_secs = row.1.parse().unwrap_or(30)
+                    }
+                    "http_ca_bundle_path" => settings.http_ca_bundle_path = row.1,
                     _ => {}
                 }
             }
@@ -776,7 +1588,12 @@ impl Database {
                 ("skip_backward_seconds", settings.skip_backward_seconds.to_string()),
                 ("skip_forward_seconds", settings.skip_forward_seconds.to_string()),
                 ("sync_interval_minutes", settings.sync_interval_minutes.to_string()),
+                ("sync_concurrency", settings.sync_concurrency.to_string()),
                 ("auto_play_next", settings.auto_play_next.to_string()),
+                ("prefetch_minutes", settings.prefetch_minutes.to_string()),
+                ("prefetch_max_mb", settings.prefetch_max_mb.to_string()),
+                ("audio_cache_memory_mb", settings.audio_cache_memory_mb.to_string()),
+                ("audio_cache_disk_mb", settings.audio_cache_disk_mb.to_string()),
                 ("download_directory", settings.download_directory.clone()),
                 (
                     "home_density",
@@ -787,8 +1604,23 @@ impl Database {
                 ),
                 ("default_speed", settings.default_speed.to_string()),
                 ("trim_silence_mode", trim_str.to_string()),
+                ("preserve_pitch", settings.preserve_pitch.to_string()),
                 ("auto_download_new_episodes", settings.auto_download_new_episodes.to_string()),
+                ("auto_download_window", settings.auto_download_window.to_string()),
+                ("auto_download_window_start", settings.auto_download_window_start.to_string()),
+                ("auto_download_window_end", settings.auto_download_window_end.to_string()),
+                ("download_bandwidth_kbps", settings.download_bandwidth_kbps.to_string()),
+                ("limit_manual_downloads", settings.limit_manual_downloads.to_string()),
                 ("global_keep_episodes_count", settings.global_keep_episodes_count.to_string()),
+                ("retention_played_after_days", settings.retention_played_after_days.to_string()),
+                ("retention_older_than_days", settings.retention_older_than_days.to_string()),
+                ("retention_keep_unplayed", settings.retention_keep_unplayed.to_string()),
+                ("download_concurrency", settings.download_concurrency.to_string()),
+                ("download_name_template", settings.download_name_template.clone()),
+                ("write_download_tags", settings.write_download_tags.to_string()),
+                ("download_quota_gb", settings.download_quota_gb.to_string()),
+                ("strip_tracking_prefixes", settings.strip_tracking_prefixes.to_string()),
+                ("tracking_prefix_rules", settings.tracking_prefix_rules.clone()),
                 ("hotkey_play_pause", settings.hotkeys.play_pause.clone()),
                 ("hotkey_next", settings.hotkeys.next.clone()),
                 ("hotkey_prev", settings.hotkeys.prev.clone()),
@@ -803,6 +1635,11 @@ impl Database {
                         ThemeMode::Light => "light".to_string(),
                     },
                 ),
+                ("http_proxy", settings.http_proxy.clone()),
+                ("http_user_agent", settings.http_user_agent.clone()),
+                ("http_connect_timeout_secs", settings.http_connect_timeout_secs.to_string()),
+                ("http_read_timeout_secs", settings.http_read_timeout_secs.to_string()),
+                ("http_ca_bundle_path", settings.http_ca_bundle_path.clone()),
             ];
 
             for (key, value) in rows {
@@ -864,6 +1701,57 @@ impl Database {
     }
 }
 
+impl Database {
+    // Network audit
+
+    // Most recent entries first, optionally narrowed to one purpose and/or a host substring.
+    pub async fn get_network_audit(
+        &self,
+        purpose: Option<RequestPurpose>,
+        host: String,
+        limit: i64,
+    ) -> anyhow::Result<Vec<NetworkAuditEntry>> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            let mut stmt = conn.prepare(
+                "SELECT timestamp, host, purpose, bytes, result
+                 FROM network_audit
+                 WHERE (?1 IS NULL OR purpose = ?1)
+                   AND (?2 = '' OR host LIKE '%' || ?2 || '%')
+                 ORDER BY id DESC
+                 LIMIT ?3",
+            )?;
+            let entries = stmt
+                .query_map(
+                    params![purpose.map(RequestPurpose::as_str), host.trim(), limit],
+                    |row| {
+                        Ok(NetworkAuditEntry {
+                            timestamp: row.get(0)?,
+                            host: row.get(1)?,
+                            purpose: RequestPurpose::from_str(&row.get::<_, String>(2)?),
+                            bytes: row.get(3)?,
+                            result: row.get(4)?,
+                        })
+                    },
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(entries)
+        })
+        .await?
+    }
+
+    pub async fn clear_network_audit(&self) -> anyhow::Result<()> {
+        let conn = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().map_err(|e| anyhow!("Lock error: {e}"))?;
+            conn.execute("DELETE FROM network_audit", [])?;
+            Ok(())
+        })
+        .await?
+    }
+}
+
 impl Database {
     pub fn get_download_directory_sync(&self) -> Result<String, DatabaseError> {
         let conn = self
@@ -885,4 +1773,98 @@ impl Database {
                 .to_string()
         }))
     }
+
+    // Every downloaded episode, oldest first.
+    pub fn get_downloaded_files_sync(&self) -> Result<Vec<DownloadedFile>, DatabaseError> {
+        let conn = self
+            .connection
+            .lock()
+            .map_err(|_| DatabaseError::LockPoisoned)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT e.id, e.podcast_id, p.title, e.downloaded_path, e.is_played, e.is_protected
+             FROM episodes e
+             JOIN podcasts p ON p.id = e.podcast_id
+             WHERE e.download_status = 'downloaded' AND e.downloaded_path IS NOT NULL
+             ORDER BY e.publish_date, e.id",
+        )?;
+        let files = stmt
+            .query_map([], |row| {
+                Ok(DownloadedFile {
+                    episode_id: row.get(0)?,
+                    podcast_id: row.get(1)?,
+                    podcast_title: row.get(2)?,
+                    path: row.get(3)?,
+                    is_played: row.get::<_, i32>(4)? != 0,
+                    is_protected: row.get::<_, i32>(5)? != 0,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(files)
+    }
+
+    pub fn clear_download_sync(&self, episode_id: i32) -> Result<(), DatabaseError> {
+        let conn = self
+            .connection
+            .lock()
+            .map_err(|_| DatabaseError::LockPoisoned)?;
+
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "UPDATE episodes SET download_status = ?1, downloaded_path = NULL,
+                 downloaded_at = NULL, updated_at = ?2
+             WHERE id = ?3",
+            params![DownloadStatus::NotDownloaded.as_str(), now, episode_id],
+        )?;
+        Ok(())
+    }
+
+    // Whether a download file belongs to an episode other than `episode_id`.
+    pub fn is_download_path_taken_sync(
+        &self,
+        path: &str,
+        episode_id: i32,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self
+            .connection
+            .lock()
+            .map_err(|_| DatabaseError::LockPoisoned)?;
+
+        let taken = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM episodes WHERE downloaded_path = ?1 AND id != ?2)",
+            params![path, episode_id],
+            |row| row.get(0),
+        )?;
+        Ok(taken)
+    }
+
+    // Called from whichever thread finished the request, so this can't go through the runtime.
+    pub fn record_network_request_sync(
+        &self,
+        timestamp: i64,
+        host: &str,
+        purpose: RequestPurpose,
+        bytes: i64,
+        result: &str,
+    ) -> Result<(), DatabaseError> {
+        let conn = self
+            .connection
+            .lock()
+            .map_err(|_| DatabaseError::LockPoisoned)?;
+
+        conn.execute(
+            "INSERT INTO network_audit (timestamp, host, purpose, bytes, result)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![timestamp, host, purpose.as_str(), bytes, result],
+        )
+        .map_err(|e| DatabaseError::generic_error(e.to_string()))?;
+
+        // Keep the log bounded; the newest entries are the interesting ones.
+        conn.execute(
+            "DELETE FROM network_audit WHERE id <= last_insert_rowid() - ?1",
+            [MAX_NETWORK_AUDIT_ENTRIES],
+        )
+        .map_err(|e| DatabaseError::generic_error(e.to_string()))?;
+        Ok(())
+    }
 }
@@ -1,10 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ports::RequestPurpose;
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum DownloadStatus {
     #[default]
     NotDownloaded,
+    // Waiting in the download queue for a free slot.
+    Queued,
     Downloading,
+    Paused,
     Downloaded,
     Failed,
 }
@@ -12,7 +17,9 @@ pub enum DownloadStatus {
 impl DownloadStatus {
     pub fn from_str(s: &str) -> Self {
         match s {
+            "queued" => Self::Queued,
             "downloading" => Self::Downloading,
+            "paused" => Self::Paused,
             "downloaded" => Self::Downloaded,
             "failed" => Self::Failed,
             _ => Self::NotDownloaded,
@@ -22,7 +29,9 @@ impl DownloadStatus {
     pub fn as_str(self) -> &'static str {
         match self {
             Self::NotDownloaded => "not_downloaded",
+            Self::Queued => "queued",
             Self::Downloading => "downloading",
+            Self::Paused => "paused",
             Self::Downloaded => "downloaded",
             Self::Failed => "failed",
         }
@@ -44,20 +53,41 @@ pub struct Podcast {
     pub speed_preset: Option<f32>,
     pub auto_download: Option<bool>,
     pub keep_episodes_count: Option<i32>,
+    pub delete_played_after_days: Option<i32>,
+    pub delete_older_than_days: Option<i32>,
+    pub keep_unplayed: Option<bool>,
     pub skip_intro_seconds: i32,
     pub skip_outro_seconds: i32,
+    // HTTP cache validators from the last feed fetch (sent back on the next sync)
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    // Background sync scheduling: per-show override (None = adaptive), the feed's own
+    // hints, and the median gap between recent episodes.
+    pub sync_interval_minutes: Option<i32>,
+    pub feed_ttl_minutes: Option<i32>,
+    pub feed_update_minutes: Option<i32>,
+    pub publish_interval_minutes: Option<i32>,
+    // Feed health: consecutive failed syncs since the last success.
+    pub last_sync_attempt_at: Option<i64>,
+    pub sync_failure_count: i32,
+    pub sync_failing_since: Option<i64>,
+    pub sync_last_error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Episode {
     pub id: i32,
     pub podcast_id: i32,
+    // Publisher-assigned identity (RSS <guid>, Atom <id>, JSON Feed id)
+    pub guid: Option<String>,
     pub title: String,
     pub description: String,
     pub url: String,
     pub audio_type: String,
     pub publish_date: i64,
     pub is_played: bool,
+    // When the episode was last marked played.
+    pub played_at: Option<i64>,
     pub duration: i64,
     pub position_seconds: f64,
     pub created_at: i64,
@@ -65,11 +95,80 @@ pub struct Episode {
     // Download tracking
     pub download_status: DownloadStatus,
     pub downloaded_path: Option<String>,
+    // When the current download finished.
+    pub downloaded_at: Option<i64>,
     pub speed_preset: Option<f32>,
     // Chapter support (Podcasting 2.0 namespace)
     pub chapters_url: Option<String>,
     // Listening statistics
     pub total_listen_seconds: i64,
+    // <itunes:season> / <itunes:episode>
+    pub season: Option<i32>,
+    pub episode_number: Option<i32>,
+    // Never deleted by retention cleanup.
+    pub is_protected: bool,
+}
+
+// An episode whose file is on disk, as used for storage accounting.
+#[derive(Debug, Clone)]
+pub struct DownloadedFile {
+    pub episode_id: i32,
+    pub podcast_id: i32,
+    pub podcast_title: String,
+    pub path: String,
+    pub is_played: bool,
+    pub is_protected: bool,
+}
+
+// One file in an interrupted or running move of the download library.
+#[derive(Debug, Clone)]
+pub struct LibraryMoveEntry {
+    pub episode_id: i32,
+    // The old download directory; emptied folders inside it are removed.
+    pub from_root: String,
+    pub from_path: String,
+    pub to_path: String,
+    pub state: LibraryMoveState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LibraryMoveState {
+    // Not yet copied to `to_path`.
+    Pending,
+    // Copied and verified; the episode still points at `from_path`.
+    Copied,
+    // The episode points at `to_path`; `from_path` is left to delete.
+    Committed,
+}
+
+impl LibraryMoveState {
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "copied" => Self::Copied,
+            "committed" => Self::Committed,
+            _ => Self::Pending,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Copied => "copied",
+            Self::Committed => "committed",
+        }
+    }
+}
+
+// One outbound HTTP request, as recorded in the network audit log.
+#[derive(Debug, Clone)]
+pub struct NetworkAuditEntry {
+    pub timestamp: i64,
+    pub host: String,
+    pub purpose: RequestPurpose,
+    // Response body bytes received.
+    pub bytes: i64,
+    // HTTP status code, or the error for requests that failed.
+    pub result: String,
 }
 
 #[derive(Debug, Clone)]
@@ -98,6 +197,30 @@ pub struct Bookmark {
     pub updated_at: i64,
 }
 
+impl Podcast {
+    // "Failing for 3 days: 404" while syncs keep failing, None when the feed is healthy.
+    pub fn sync_health(&self) -> Option<String> {
+        if self.sync_failure_count == 0 {
+            return None;
+        }
+        let since = self.sync_failing_since.unwrap_or_else(|| chrono::Utc::now().timestamp());
+        let diff = chrono::Utc::now().timestamp() - since;
+        let duration = if diff < 3600 {
+            format!("{}m", (diff / 60).max(1))
+        } else if diff < 86400 {
+            let hours = diff / 3600;
+            format!("{} hour{}", hours, if hours == 1 { "" } else { "s" })
+        } else {
+            let days = diff / 86400;
+            format!("{} day{}", days, if days == 1 { "" } else { "s" })
+        };
+        Some(match &self.sync_last_error {
+            Some(error) => format!("Failing for {duration}: {error}"),
+            None => format!("Failing for {duration}"),
+        })
+    }
+}
+
 impl Episode {
     pub fn format_publish_date(&self) -> String {
         let now = chrono::Utc::now().timestamp();
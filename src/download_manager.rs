@@ -1,115 +1,507 @@
+use crate::bandwidth::BandwidthLimiter;
+use crate::chapters::Chapter;
 use crate::db::Database;
-use crate::utils::string_utils::{sanitize_file_name, sanitize_folder_uri};
-use reqwest::blocking::Client;
+use crate::db::models::{DownloadedFile, Episode, Podcast};
+use crate::download_naming::DownloadName;
+use crate::download_tags::{self, EpisodeTags};
+use crate::errors::DownloadError;
+use crate::image_cache::ImageCache;
+use crate::ports::{HttpError, HttpFetcher, HttpRequest, HttpResponse, RequestPurpose};
+use crate::tracking_prefixes::{self, TrackingPrefixes};
+use crate::utils::string_utils::sanitize_folder_uri;
+use rodio::Decoder;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+// Suffix for downloads that haven't finished yet.
+const PART_EXTENSION: &str = "part";
+
+// Minimum time between two progress callbacks.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// With no size from the server, room under the quota is claimed this much at a time.
+const RESERVE_STEP: u64 = 32 * 1024 * 1024;
+
+// Samples decoded to check that a finished download is playable (about a tenth of a second).
+const DECODE_CHECK_SAMPLES: usize = 8192;
+
+/// How a download reports back, and what can hold it back or cut it short.
+pub struct DownloadControl<'a> {
+    // Receives the bytes written so far and the total size, when the server reports one.
+    pub on_progress: &'a mut dyn FnMut(u64, Option<u64>),
+    // Given the `.part` file's path and the size it is about to grow to, before it does; can
+    // refuse the download (e.g. when it would go over the storage quota). That is the whole
+    // file when the server reports its size, or the next `RESERVE_STEP` bytes at a time when
+    // it doesn't.
+    pub reserve: &'a mut dyn FnMut(&Path, u64) -> Result<(), DownloadError>,
+    // Share the bandwidth cap with every other limited download.
+    pub limited: bool,
+    // Ends the download early with an error, leaving the `.part` file in place.
+    pub stop: &'a AtomicBool,
+}
 
 #[derive(Clone)]
 pub struct DownloadManager {
     database: Database,
-    client: Client,
+    http: Arc<dyn HttpFetcher>,
+    tracking_prefixes: Arc<RwLock<Option<TrackingPrefixes>>>,
+    bandwidth: Arc<BandwidthLimiter>,
 }
 
 impl DownloadManager {
-    pub fn new(database: Database) -> Self {
+    pub fn new(database: Database, http: Arc<dyn HttpFetcher>) -> Self {
         Self {
             database,
-            client: Client::new(),
+            http,
+            tracking_prefixes: Arc::new(RwLock::new(None)),
+            bandwidth: Arc::new(BandwidthLimiter::new()),
         }
     }
 
-    pub fn find_file(&self, folders: Vec<String>, file_name: &str) -> Option<PathBuf> {
-        let download_path = self.database.get_download_directory_sync().ok()?;
-        let download_dir = construct_download_path(download_path, folders);
+    // None turns prefix stripping off. Shared by all clones of this manager.
+    pub fn set_tracking_prefixes(&self, prefixes: Option<TrackingPrefixes>) {
+        *self.tracking_prefixes.write().unwrap() = prefixes;
+    }
+
+    // Caps the combined speed of limited downloads. None removes the cap.
+    pub fn set_bandwidth_limit(&self, bytes_per_sec: Option<u64>) {
+        self.bandwidth.set_rate(bytes_per_sec);
+    }
+
+    /// Downloads the file and returns the path where it was saved.
+    ///
+    /// Data goes to a `.part` file next to the destination, which is renamed into place only
+    /// once the whole body has arrived. If a `.part` file is already there from an earlier
+    /// attempt, the download resumes from where it stopped. `control` receives progress,
+    /// approves the space the file takes, and can stop the download.
+    ///
+    /// Before the file is moved into place it has to pass a few checks: a 2xx response with an
+    /// audio or video content type, a body matching `Content-Length`, and a first few frames
+    /// that rodio can decode. A file failing any of them is thrown away.
+    pub fn download(
+        &self,
+        url: String,
+        episode_id: i32,
+        name: &DownloadName,
+        control: DownloadControl,
+    ) -> Result<PathBuf, DownloadError> {
+        let DownloadControl {
+            on_progress,
+            reserve,
+            limited,
+            stop,
+        } = control;
+        let download_path = self
+            .database
+            .get_download_directory_sync()
+            .map_err(|e| DownloadError::Io(e.to_string()))?;
+
+        let download_dir = construct_download_path(download_path, &name.folders);
 
-        let entries = fs::read_dir(&download_dir).ok()?;
-        let sanitized = sanitize_file_name(file_name);
+        if !download_dir.exists() {
+            fs::create_dir_all(&download_dir)?;
+        }
+
+        let part_path = download_dir.join(part_file_name(name, episode_id));
+        let prefixes = self.tracking_prefixes.read().unwrap().clone();
 
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-            if name_str.starts_with(sanitized.as_str()) {
-                return Some(entry.path());
+        let mut resume_from = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+        let (mut response, start, total) = loop {
+            let mut request = HttpRequest::get(RequestPurpose::Download, &url);
+            if resume_from > 0 {
+                request = request.header("Range", format!("bytes={resume_from}-"));
             }
+            let response = tracking_prefixes::get_direct(self.http.as_ref(), request, prefixes.as_ref());
+
+            match response {
+                Ok(response) if response.status == 206 => {
+                    match parse_content_range(response.header("content-range")) {
+                        Some((start, total)) if start == resume_from => break (response, start, total),
+                        // The server sent a different range than asked for; start over.
+                        _ => resume_from = 0,
+                    }
+                }
+                Ok(response) => {
+                    let total = response
+                        .header("content-length")
+                        .and_then(|v| v.trim().parse().ok());
+                    break (response, 0, total);
+                }
+                // The partial file no longer fits the remote one (it changed, or the part is
+                // already complete but was never renamed). Fetch it again from scratch.
+                Err(HttpError::Status(416)) if resume_from > 0 => resume_from = 0,
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        if !(200..300).contains(&response.status) {
+            return Err(DownloadError::Status(response.status));
         }
+        if let Some(mime) = response.header("content-type")
+            && !is_media_type(mime)
+        {
+            return Err(DownloadError::NotMedia(mime.to_string()));
+        }
+        let mut reserved = total.unwrap_or(start + RESERVE_STEP);
+        reserve(&part_path, reserved)?;
+
+        let extension = file_extension(&response, &url);
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(start > 0)
+            .truncate(start == 0)
+            .open(&part_path)?;
+
+        let mut written = start;
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut last_report = Instant::now();
+        on_progress(written, total);
+
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return Err(DownloadError::Stopped);
+            }
+            let chunk = if limited {
+                self.bandwidth.chunk_size(buf.len())
+            } else {
+                buf.len()
+            };
+            let n = match response.body.read(&mut buf[..chunk]) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                // Keep the .part file so the next attempt can resume.
+                Err(e) => return Err(DownloadError::Network(e.to_string())),
+            };
+            if written + n as u64 > reserved {
+                reserved = written + n as u64 + RESERVE_STEP;
+                reserve(&part_path, reserved)?;
+            }
+            file.write_all(&buf[..n])?;
+            written += n as u64;
+            if limited {
+                self.bandwidth.consume(n, stop);
+            }
+
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                last_report = Instant::now();
+                on_progress(written, total);
+            }
+        }
+
+        file.sync_all()?;
+        drop(file);
 
-        None
+        // A short body is left in place so the next attempt can resume it; anything else
+        // that fails from here on is bad data and is deleted.
+        if let Some(expected) = total
+            && written < expected
+        {
+            return Err(DownloadError::Incomplete {
+                received: written,
+                expected,
+            });
+        }
+        let checked = match total {
+            _ if written == 0 => Err(DownloadError::Empty),
+            Some(expected) if written != expected => Err(DownloadError::SizeMismatch {
+                received: written,
+                expected,
+            }),
+            _ => check_decodes(&part_path, &extension),
+        };
+        if let Err(e) = checked {
+            let _ = fs::remove_file(&part_path);
+            return Err(e);
+        }
+        on_progress(written, total);
+
+        let final_path = self.free_path(&download_dir, &name.stem, &extension, episode_id);
+        fs::rename(&part_path, &final_path)?;
+        Ok(final_path)
     }
 
-    /// Downloads the file and returns the path where it was saved.
-    pub fn download(
+    /// Moves an existing download to where `name` puts it, keeping its extension, and returns
+    /// the new path. Folders left empty behind it are removed.
+    pub fn relocate(
         &self,
-        url: String,
-        folders: Vec<String>,
-        file_name: String,
+        current: &Path,
+        name: &DownloadName,
+        episode_id: i32,
     ) -> Result<PathBuf, String> {
         let download_path = self
             .database
             .get_download_directory_sync()
             .map_err(|e| e.to_string())?;
+        let download_dir = construct_download_path(download_path.clone(), &name.folders);
 
-        let mut download_dir = construct_download_path(download_path, folders);
+        let extension = current
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let wanted = download_dir.join(format!("{}{extension}", name.stem));
+        if wanted == current {
+            return Ok(wanted);
+        }
 
-        if !download_dir.exists() {
-            fs::create_dir_all(&download_dir).map_err(|e| e.to_string())?;
-        }
-
-        let mut response = self
-            .client
-            .get(&url)
-            .send()
-            .map_err(|_| "Failed to download file".to_string())?;
-
-        let headers = response.headers().clone();
-
-        let ext = headers
-            .get("content-disposition")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| {
-                v.split(';')
-                    .map(str::trim)
-                    .find(|p| p.to_lowercase().starts_with("filename="))
-                    .and_then(|p| p.split_once('=').map(|x| x.1))
-                    .map(|f| f.trim_matches('"').to_string())
-            })
-            .and_then(|filename| {
-                Path::new(&filename)
-                    .extension()
-                    .map(|e| format!(".{}", e.to_string_lossy()))
-            })
-            .or_else(|| {
-                headers
-                    .get("content-type")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(mime_to_ext)
-                    .map(|e| e.to_string())
+        fs::create_dir_all(&download_dir).map_err(|e| e.to_string())?;
+        let target = self.free_path(&download_dir, &name.stem, &extension, episode_id);
+        fs::rename(current, &target).map_err(|e| e.to_string())?;
+
+        // Only empty folders are removed, so stop at the first one that still has files.
+        let root = Path::new(&download_path);
+        let mut dir = current.parent();
+        while let Some(d) = dir {
+            if d == root || !d.starts_with(root) || fs::remove_dir(d).is_err() {
+                break;
+            }
+            dir = d.parent();
+        }
+
+        Ok(target)
+    }
+
+    // Removes what an unfinished download left behind.
+    pub fn discard_partial(&self, name: &DownloadName, episode_id: i32) {
+        if let Ok(download_path) = self.database.get_download_directory_sync() {
+            let dir = construct_download_path(download_path, &name.folders);
+            let _ = fs::remove_file(dir.join(part_file_name(name, episode_id)));
+        }
+    }
+
+    // Downloaded files with their size on disk, oldest episode first. Files that have gone
+    // missing are left out.
+    pub fn downloaded_files(&self) -> Vec<(DownloadedFile, u64)> {
+        self.database
+            .get_downloaded_files_sync()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|file| {
+                let size = fs::metadata(&file.path).ok()?.len();
+                Some((file, size))
             })
-            .unwrap_or_else(|| ".bin".to_string());
+            .collect()
+    }
+
+    // Total size of the unfinished downloads under the download folder.
+    fn partial_bytes(&self) -> u64 {
+        self.database
+            .get_download_directory_sync()
+            .map(|root| part_files_size(Path::new(&root)))
+            .unwrap_or(0)
+    }
 
-        download_dir = download_dir.join(format!("{}{}", sanitize_file_name(&file_name), ext));
+    /// Frees space for `needed` more bytes under `quota` by deleting played downloads, oldest
+    /// episode first, calling `on_evicted` with each episode it removes. Unfinished downloads
+    /// count towards the quota too. `keep` and protected episodes are never removed.
+    /// Nothing is deleted if even removing every played download would not make enough room.
+    pub fn make_room(
+        &self,
+        needed: u64,
+        quota: u64,
+        keep: i32,
+        on_evicted: &mut dyn FnMut(i32),
+    ) -> Result<(), DownloadError> {
+        let files = self.downloaded_files();
+        let used: u64 = files.iter().map(|(_, size)| size).sum::<u64>() + self.partial_bytes();
+        let mut excess = (used + needed).saturating_sub(quota);
+        if excess == 0 {
+            return Ok(());
+        }
+
+        let candidates: Vec<_> = files
+            .into_iter()
+            .filter(|(file, _)| file.is_played && !file.is_protected && file.episode_id != keep)
+            .collect();
+        if candidates.iter().map(|(_, size)| size).sum::<u64>() < excess {
+            return Err(DownloadError::QuotaExceeded { needed, quota });
+        }
 
-        let mut file =
-            fs::File::create(&download_dir).map_err(|_| "Failed to create file".to_string())?;
-        response
-            .copy_to(&mut file)
-            .map_err(|_| "Failed to write file".to_string())?;
+        // Only files that were actually deleted free anything; one that can't be is passed
+        // over for the next.
+        for (file, size) in candidates {
+            if excess == 0 {
+                break;
+            }
+            if self.remove_download(&file).is_ok() {
+                excess = excess.saturating_sub(size);
+                on_evicted(file.episode_id);
+            }
+        }
+        if excess > 0 {
+            return Err(DownloadError::QuotaExceeded { needed, quota });
+        }
+        Ok(())
+    }
 
-        Ok(download_dir)
+    // Deletes the file and marks the episode as not downloaded.
+    pub fn remove_download(&self, file: &DownloadedFile) -> Result<(), String> {
+        self.delete_file(&file.path)?;
+        self.database
+            .clear_download_sync(file.episode_id)
+            .map_err(|e| e.to_string())
+    }
+
+    // Tags a finished download with the episode's metadata, the podcast artwork and the
+    // given chapters. Files in formats we can't tag are skipped.
+    pub fn write_tags(
+        &self,
+        path: &Path,
+        podcast: &Podcast,
+        episode: &Episode,
+        chapters: Vec<Chapter>,
+    ) -> Result<(), String> {
+        if !download_tags::can_tag(path) {
+            return Ok(());
+        }
+        let tags = EpisodeTags {
+            title: episode.title.clone(),
+            podcast: podcast.title.clone(),
+            publish_date: episode.publish_date,
+            description: episode.description.clone(),
+            duration: episode.duration,
+            artwork: ImageCache::load_bytes(self.http.as_ref(), &podcast.image_url),
+            chapters,
+        };
+        download_tags::write(path, &tags)
     }
 
     pub fn delete_file(&self, path: &str) -> Result<(), String> {
         fs::remove_file(path).map_err(|e| e.to_string())
     }
+
+    // `stem.ext`, or `stem_2.ext`, `stem_3.ext`, ... if another file is already there.
+    fn free_path(&self, dir: &Path, stem: &str, extension: &str, episode_id: i32) -> PathBuf {
+        let mut path = dir.join(format!("{stem}{extension}"));
+        let mut n = 2;
+        while path.exists() || self.is_taken(&path, episode_id) {
+            path = dir.join(format!("{stem}_{n}{extension}"));
+            n += 1;
+        }
+        path
+    }
+
+    fn is_taken(&self, path: &Path, episode_id: i32) -> bool {
+        self.database
+            .is_download_path_taken_sync(&path.to_string_lossy(), episode_id)
+            .unwrap_or(false)
+    }
 }
 
-fn construct_download_path(base_path: String, folders: Vec<String>) -> PathBuf {
+fn part_files_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => part_files_size(&path),
+                Ok(t) if t.is_file() && path.extension().is_some_and(|e| e == PART_EXTENSION) => {
+                    entry.metadata().map(|m| m.len()).unwrap_or(0)
+                }
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+// Keyed by episode so two episodes with the same name never share a partial file.
+fn part_file_name(name: &DownloadName, episode_id: i32) -> String {
+    format!("{}.{episode_id}.{PART_EXTENSION}", name.stem)
+}
+
+fn construct_download_path(base_path: String, folders: &[String]) -> PathBuf {
     let mut dir = Path::new(&base_path).to_path_buf();
     for folder in folders {
-        dir = dir.join(sanitize_folder_uri(&folder));
+        dir = dir.join(sanitize_folder_uri(folder));
     }
     dir
 }
 
+// Extension for the saved file, from Content-Disposition, then Content-Type, then the
+// enclosure URL's path.
+fn file_extension(response: &HttpResponse, url: &str) -> String {
+    response
+        .header("content-disposition")
+        .and_then(|v| {
+            v.split(';')
+                .map(str::trim)
+                .find(|p| p.to_lowercase().starts_with("filename="))
+                .and_then(|p| p.split_once('=').map(|x| x.1))
+                .map(|f| f.trim_matches('"').to_string())
+        })
+        .and_then(|filename| {
+            Path::new(&filename)
+                .extension()
+                .map(|e| format!(".{}", e.to_string_lossy()))
+        })
+        .or_else(|| {
+            response
+                .header("content-type")
+                .and_then(mime_to_ext)
+                .map(|e| e.to_string())
+        })
+        .or_else(|| url_extension(url))
+        .unwrap_or_else(|| ".bin".to_string())
+}
+
+// `https://host/show/ep42.m4a?src=rss` -> `.m4a`.
+fn url_extension(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    let last = url.path_segments()?.next_back()?;
+    let extension = Path::new(last).extension()?.to_str()?;
+    let plausible = !extension.is_empty()
+        && extension.len() <= 5
+        && extension.chars().all(|c| c.is_ascii_alphanumeric());
+    plausible.then(|| format!(".{}", extension.to_lowercase()))
+}
+
+// Content types a podcast enclosure can legitimately be served with. Many hosts send
+// audio as a generic binary stream, which the decode check then has to vouch for.
+fn is_media_type(mime: &str) -> bool {
+    let mime = mime.split(';').next().unwrap_or("").trim().to_lowercase();
+    mime.is_empty()
+        || mime.starts_with("audio/")
+        || mime.starts_with("video/")
+        || matches!(
+            mime.as_str(),
+            "application/ogg" | "application/octet-stream" | "binary/octet-stream"
+        )
+}
+
+// Decodes the first frames of a finished download. `extension` is passed on as a format hint.
+fn check_decodes(path: &Path, extension: &str) -> Result<(), DownloadError> {
+    let file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut builder = Decoder::builder().with_data(file).with_byte_len(len);
+    if let Some(ext) = extension.strip_prefix('.') {
+        builder = builder.with_hint(ext);
+    }
+    let decoder = builder
+        .build()
+        .map_err(|e| DownloadError::Undecodable(e.to_string()))?;
+    if decoder.take(DECODE_CHECK_SAMPLES).count() == 0 {
+        return Err(DownloadError::Undecodable("no audio frames".to_string()));
+    }
+    Ok(())
+}
+
+// `bytes 100-199/1000` -> (100, Some(1000)). The total is None when the server sends `*`.
+pub fn parse_content_range(value: Option<&str>) -> Option<(u64, Option<u64>)> {
+    let range = value?.trim().strip_prefix("bytes ")?;
+    let (span, total) = range.split_once('/')?;
+    let start = span.split_once('-')?.0.trim().parse().ok()?;
+    Some((start, total.trim().parse().ok()))
+}
+
 fn mime_to_ext(mime: &str) -> Option<&'static str> {
     let mime = mime.split(';').next()?.trim();
     match mime {
@@ -117,6 +509,8 @@ fn mime_to_ext(mime: &str) -> Option<&'static str> {
         "audio/ogg" => Some(".ogg"),
         "audio/wav" => Some(".wav"),
         "audio/flac" => Some(".flac"),
+        "audio/mp4" | "audio/x-m4a" | "audio/m4a" => Some(".m4a"),
+        "audio/aac" => Some(".aac"),
         "video/mp4" => Some(".mp4"),
         "video/webm" => Some(".webm"),
         "image/jpeg" => Some(".jpg"),
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use crate::db::models::{Episode, Podcast};
+use crate::utils::string_utils::{sanitize_file_name, sanitize_folder_uri};
+
+// Matches the layout used before templates existed.
+pub const DEFAULT_TEMPLATE: &str = "{podcast}/{title}.{ext}";
+
+// Placeholders a template may use, with a short description for the settings page.
+pub const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("podcast", "podcast title"),
+    ("title", "episode title"),
+    ("yyyy", "publish year"),
+    ("mm", "publish month"),
+    ("dd", "publish day"),
+    ("season", "season number"),
+    ("episode", "episode number"),
+    ("ext", "file extension"),
+];
+
+/// Where an episode's download lives, relative to the download directory.
+///
+/// Built from the naming template in the settings, e.g. `{podcast}/{yyyy}-{mm}-{dd} {title}.{ext}`.
+/// `/` separates folders. Every part is sanitized for the filesystem, and the extension is
+/// always taken from the server's response, so `{ext}` may only appear at the very end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadName {
+    pub folders: Vec<String>,
+    // File name without the extension.
+    pub stem: String,
+}
+
+impl DownloadName {
+    // Falls back to the default template if the configured one is invalid.
+    pub fn new(template: &str, podcast: &Podcast, episode: &Episode) -> Self {
+        let template = if validate_template(template).is_ok() {
+            template
+        } else {
+            DEFAULT_TEMPLATE
+        };
+
+        let template = template
+            .strip_suffix(".{ext}")
+            .or_else(|| template.strip_suffix("{ext}"))
+            .unwrap_or(template);
+
+        let mut segments: Vec<String> = template
+            .split('/')
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| render(s, podcast, episode))
+            .collect();
+        let stem = sanitize_file_name(&segments.pop().unwrap_or_default());
+        let folders = segments.iter().map(|s| sanitize_folder_uri(s)).collect();
+
+        Self { folders, stem }
+    }
+
+    // Where the file goes without its extension, relative to the download directory.
+    pub fn relative_stem(&self) -> PathBuf {
+        let mut path: PathBuf = self.folders.iter().collect();
+        path.push(&self.stem);
+        path
+    }
+}
+
+// Checks a template before it is saved. The error is shown next to the setting.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("Template is empty".to_string());
+    }
+
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            return Err("Unclosed '{'".to_string());
+        };
+        let name = &after[..close];
+        if !PLACEHOLDERS.iter().any(|(p, _)| *p == name) {
+            return Err(format!("Unknown placeholder {{{name}}}"));
+        }
+        rest = &after[close + 1..];
+        if name == "ext" && !rest.is_empty() {
+            return Err("{ext} must come last".to_string());
+        }
+    }
+
+    let file_part = template.rsplit('/').next().unwrap_or("");
+    if file_part
+        .replace("{ext}", "")
+        .trim_matches(['.', ' '])
+        .is_empty()
+    {
+        return Err("Template has no file name".to_string());
+    }
+    Ok(())
+}
+
+// Single pass, so braces inside titles are left alone. Assumes a validated template.
+fn render(segment: &str, podcast: &Podcast, episode: &Episode) -> String {
+    let date = chrono::DateTime::from_timestamp(episode.publish_date, 0).unwrap_or_default();
+    // Missing numbers render as 00 so names still sort.
+    let number = |n: Option<i32>| format!("{:02}", n.unwrap_or(0));
+
+    let mut out = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            break;
+        };
+        out.push_str(&rest[..open]);
+        match &after[..close] {
+            "podcast" => out.push_str(&podcast.title),
+            "title" => out.push_str(&episode.title),
+            "yyyy" => out.push_str(&date.format("%Y").to_string()),
+            "mm" => out.push_str(&date.format("%m").to_string()),
+            "dd" => out.push_str(&date.format("%d").to_string()),
+            "season" => out.push_str(&number(episode.season)),
+            "episode" => out.push_str(&number(episode.episode_number)),
+            _ => {}
+        }
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
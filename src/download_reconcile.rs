@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::db::Database;
+use crate::db::models::{DownloadStatus, Episode, Podcast};
+use crate::download_naming::{DEFAULT_TEMPLATE, DownloadName};
+use crate::types::UnknownFile;
+
+// Suffix of unfinished downloads, which belong to the download queue.
+const PART_EXTENSION: &str = "part";
+
+/// What a reconciliation pass found and fixed.
+#[derive(Debug, Default)]
+pub struct Reconciliation {
+    // Episodes marked downloaded whose file is gone; now marked not downloaded.
+    pub missing: Vec<i32>,
+    // Episodes matched to a file the database didn't know about, with its path.
+    pub found: Vec<(i32, String)>,
+    // Files in podcast folders that belong to no episode. Left for the user to delete.
+    pub unknown: Vec<UnknownFile>,
+    // The download folder isn't there, or none of the files the database points at are, so
+    // nothing was changed: a drive that isn't mounted looks just like every download having
+    // been deleted.
+    pub folder_unavailable: bool,
+}
+
+/// Brings the download state in the database in line with the download folder.
+///
+/// A file is matched back to an episode when it sits where the naming template (or the
+/// default template, for files saved before it was changed) would put that episode. Only
+/// folders that downloads go into are scanned, since the download directory may be shared
+/// with other data; loose files at its top level are matched but never reported as unknown.
+/// Episodes that are queued or downloading are left alone. When the folder looks unavailable,
+/// nothing is changed unless `clear_missing` says to go ahead anyway.
+pub async fn reconcile_downloads(
+    db: &Database,
+    template: &str,
+    clear_missing: bool,
+) -> anyhow::Result<Reconciliation> {
+    let mut library = Vec::new();
+    for podcast in db.get_all_podcasts().await? {
+        let episodes = db.get_episodes(podcast.id).await?;
+        library.push((podcast, episodes));
+    }
+
+    let scan_db = db.clone();
+    let template = template.to_string();
+    let report = tokio::task::spawn_blocking(move || -> anyhow::Result<Reconciliation> {
+        let root = PathBuf::from(scan_db.get_download_directory_sync()?);
+        Ok(scan(&root, &library, &template))
+    })
+    .await??;
+
+    if report.folder_unavailable && !clear_missing {
+        return Ok(report);
+    }
+    for &episode_id in &report.missing {
+        db.update_episode_download_status(episode_id, DownloadStatus::NotDownloaded, None)
+            .await?;
+    }
+    for (episode_id, path) in &report.found {
+        db.update_episode_download_status(
+            *episode_id,
+            DownloadStatus::Downloaded,
+            Some(path.clone()),
+        )
+        .await?;
+    }
+    Ok(report)
+}
+
+fn scan(root: &Path, library: &[(Podcast, Vec<Episode>)], template: &str) -> Reconciliation {
+    let mut report = Reconciliation::default();
+    // Files the database already points at.
+    let mut claimed: HashSet<PathBuf> = HashSet::new();
+    // Every episode by the name it would be downloaded under, and whether it may take a
+    // file that turns up there.
+    let mut by_name: HashMap<PathBuf, Vec<(i32, bool)>> = HashMap::new();
+    // Top-level folders that hold downloads.
+    let mut folders: HashSet<PathBuf> = HashSet::new();
+
+    for (podcast, episodes) in library {
+        for episode in episodes {
+            let can_adopt = match episode.download_status {
+                DownloadStatus::Downloaded => match episode.downloaded_path.as_deref() {
+                    Some(path) if Path::new(path).is_file() => {
+                        claimed.insert(PathBuf::from(path));
+                        if let Ok(relative) = Path::new(path).strip_prefix(root) {
+                            folders.extend(top_folder(relative));
+                        }
+                        false
+                    }
+                    _ => {
+                        report.missing.push(episode.id);
+                        true
+                    }
+                },
+                DownloadStatus::NotDownloaded | DownloadStatus::Failed => true,
+                DownloadStatus::Queued | DownloadStatus::Downloading | DownloadStatus::Paused => {
+                    false
+                }
+            };
+
+            for template in [template, DEFAULT_TEMPLATE] {
+                let key = DownloadName::new(template, podcast, episode).relative_stem();
+                folders.extend(top_folder(&key));
+                let entry = by_name.entry(key).or_default();
+                if !entry.iter().any(|(id, _)| *id == episode.id) {
+                    entry.push((episode.id, can_adopt));
+                }
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for folder in &folders {
+        collect_files(&root.join(folder), &mut files);
+    }
+    if let Ok(entries) = fs::read_dir(root) {
+        for entry in entries.flatten() {
+            if let Some(bytes) = media_file_size(&entry.path()) {
+                files.push((entry.path(), bytes));
+            }
+        }
+    }
+
+    let mut adopted = HashSet::new();
+    for (path, bytes) in files {
+        if claimed.contains(&path) {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        match owners(&by_name, relative) {
+            Some(owners) => {
+                if let Some(&(episode_id, _)) = owners
+                    .iter()
+                    .find(|(id, can_adopt)| *can_adopt && !adopted.contains(id))
+                {
+                    adopted.insert(episode_id);
+                    report
+                        .found
+                        .push((episode_id, path.to_string_lossy().to_string()));
+                }
+            }
+            None if relative.parent().is_some_and(|p| p != Path::new("")) => {
+                report.unknown.push(UnknownFile { path, bytes });
+            }
+            None => {}
+        }
+    }
+
+    report.missing.retain(|id| !adopted.contains(id));
+    report.folder_unavailable =
+        !report.missing.is_empty() && (!root.is_dir() || claimed.is_empty());
+    report.unknown.sort_by(|a, b| a.path.cmp(&b.path));
+    report
+}
+
+// The episodes a file could belong to. Copies saved as `name_2.mp3`, `name_3.mp3`, ... when
+// the name was taken count as `name.mp3`.
+fn owners<'a>(
+    by_name: &'a HashMap<PathBuf, Vec<(i32, bool)>>,
+    relative: &Path,
+) -> Option<&'a Vec<(i32, bool)>> {
+    let key = relative.with_extension("");
+    by_name.get(&key).or_else(|| {
+        let stem = key.file_name()?.to_str()?;
+        let (base, n) = stem.rsplit_once('_')?;
+        n.parse::<u32>().ok()?;
+        by_name.get(&key.with_file_name(base))
+    })
+}
+
+fn top_folder(relative: &Path) -> Option<PathBuf> {
+    let mut components = relative.components();
+    let first = components.next()?;
+    // A bare file name has no folder.
+    components.next()?;
+    Some(PathBuf::from(first.as_os_str()))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<(PathBuf, u64)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if let Some(bytes) = media_file_size(&path) {
+            files.push((path, bytes));
+        }
+    }
+}
+
+// Size of a file that could be a download. Hidden files and partial downloads are skipped.
+fn media_file_size(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    if name.starts_with('.') || path.extension().is_some_and(|e| e == PART_EXTENSION) {
+        return None;
+    }
+    let metadata = fs::metadata(path).ok()?;
+    metadata.is_file().then_some(metadata.len())
+}
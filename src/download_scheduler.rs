@@ -0,0 +1,486 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use tokio::sync::Notify;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::chapters;
+use crate::db::Database;
+use crate::db::models::{DownloadStatus, Episode, Podcast};
+use crate::download_manager::{DownloadControl, DownloadManager};
+use crate::download_naming::DownloadName;
+use crate::errors::DownloadError;
+use crate::events::AppEvent;
+use crate::ports::HttpFetcher;
+use crate::retention;
+use crate::types::Settings;
+
+// Attempts before a failing download is taken out of the queue.
+const MAX_ATTEMPTS: i32 = 5;
+
+// Wait before the first retry; each further failure quadruples it.
+const RETRY_BASE_SECS: i64 = 60;
+const MAX_RETRY_DELAY_SECS: i64 = 6 * 60 * 60;
+
+/// Runs the persisted download queue.
+///
+/// Manual and automatic downloads are both queued here and started as slots free up, up to
+/// `Settings::download_concurrency` at a time. Automatic downloads wait for the auto-download
+/// window when one is set. Failed downloads are retried with exponential backoff; paused ones
+/// keep their `.part` file and resume where they stopped.
+#[derive(Clone)]
+pub struct DownloadScheduler {
+    db: Database,
+    download_manager: DownloadManager,
+    event_tx: UnboundedSender<AppEvent>,
+    http: Arc<dyn HttpFetcher>,
+    settings: Arc<RwLock<Settings>>,
+    active: Arc<Mutex<HashMap<i32, Running>>>,
+    // Room under the storage quota claimed by running downloads. The lock also makes
+    // downloads claim room one at a time.
+    reservations: Arc<Mutex<HashMap<i32, Reservation>>>,
+    wake: Arc<Notify>,
+}
+
+// A running download's claim: its `.part` file may grow to `size` bytes.
+struct Reservation {
+    part: PathBuf,
+    size: u64,
+}
+
+impl Reservation {
+    // What the file has yet to grow by.
+    fn outstanding(&self) -> u64 {
+        let written = std::fs::metadata(&self.part).map(|m| m.len()).unwrap_or(0);
+        self.size.saturating_sub(written)
+    }
+}
+
+// A download that has been started.
+struct Running {
+    stop: Arc<AtomicBool>,
+    // Cancelled rather than paused: the download deletes what it wrote once it has stopped.
+    discard: bool,
+}
+
+impl DownloadScheduler {
+    pub fn new(
+        db: Database,
+        download_manager: DownloadManager,
+        event_tx: UnboundedSender<AppEvent>,
+        http: Arc<dyn HttpFetcher>,
+    ) -> Self {
+        Self {
+            db,
+            download_manager,
+            event_tx,
+            http,
+            settings: Arc::new(RwLock::new(Settings::default())),
+            active: Arc::new(Mutex::new(HashMap::new())),
+            reservations: Arc::new(Mutex::new(HashMap::new())),
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn set_settings(&self, settings: Settings) {
+        *self.settings.write().unwrap() = settings;
+        self.wake.notify_one();
+    }
+
+    // Resolves when the queue may have work to start.
+    pub async fn woken(&self) {
+        self.wake.notified().await;
+    }
+
+    // Picks up whatever was queued when the app last closed.
+    pub async fn restore(&self) {
+        if let Err(e) = self.db.restore_download_queue().await {
+            eprintln!("[downloads] could not restore queue: {e}");
+        }
+        self.wake.notify_one();
+    }
+
+    pub async fn enqueue(&self, episode_id: i32, is_auto: bool) {
+        if self.is_running(episode_id) {
+            return;
+        }
+        // A download that is still winding down after a pause or cancel is queued all the
+        // same; `pump` starts it again once the old run has finished.
+        match self.db.enqueue_download(episode_id, is_auto).await {
+            Ok(()) => {
+                self.send_status(episode_id, DownloadStatus::Queued, None);
+                self.wake.notify_one();
+            }
+            Err(e) => {
+                let _ = self
+                    .event_tx
+                    .send(AppEvent::Error(format!("Could not queue download: {e}")));
+            }
+        }
+    }
+
+    // Stops the download but keeps its progress, so it can be resumed with `enqueue`.
+    pub async fn pause(&self, episode_id: i32) {
+        self.stop(episode_id);
+        if let Ok(true) = self.db.pause_queued_download(episode_id).await {
+            self.send_status(episode_id, DownloadStatus::Paused, None);
+        }
+    }
+
+    // Stops the download, drops it from the queue and deletes any partial file.
+    pub async fn cancel(&self, episode_id: i32) {
+        // A running download may still be writing its partial file, so it deletes the file
+        // itself once it has stopped.
+        let running = match self.active.lock().unwrap().get_mut(&episode_id) {
+            Some(running) => {
+                running.stop.store(true, Ordering::Relaxed);
+                running.discard = true;
+                true
+            }
+            None => false,
+        };
+        let _ = self.db.remove_queued_download(episode_id).await;
+        if !running
+            && let Ok(Some(episode)) = self.db.get_episode(episode_id).await
+            && let Ok(Some(podcast)) = self.db.get_podcast(episode.podcast_id).await
+        {
+            let name = self.download_name(&podcast, &episode);
+            self.download_manager.discard_partial(&name, episode_id);
+        }
+        self.set_status(episode_id, DownloadStatus::NotDownloaded, None)
+            .await;
+    }
+
+    // Deletes the podcast's downloads that its retention rules no longer keep.
+    pub async fn apply_retention(&self, podcast_id: i32) {
+        let settings = self.settings.read().unwrap().clone();
+        retention::enforce_retention_policy(
+            podcast_id,
+            &self.db,
+            &self.download_manager,
+            &settings,
+            &self.event_tx,
+        )
+        .await;
+    }
+
+    // Starts queued downloads while there are free slots.
+    pub async fn pump(&self) {
+        let limit = self.settings.read().unwrap().download_concurrency.max(1) as usize;
+        let running = self.active.lock().unwrap().len();
+        if running >= limit {
+            return;
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let include_auto = self
+            .settings
+            .read()
+            .unwrap()
+            .allows_auto_download_at(local_minute_of_day());
+        // Ask for enough rows to skip over the ones that are already running.
+        let Ok(due) = self
+            .db
+            .get_due_downloads(now, (limit + running) as i64, include_auto)
+            .await
+        else {
+            return;
+        };
+
+        let mut free = limit - running;
+        for (episode_id, is_auto) in due {
+            if free == 0 {
+                break;
+            }
+            let stop = Arc::new(AtomicBool::new(false));
+            {
+                let mut active = self.active.lock().unwrap();
+                if active.contains_key(&episode_id) {
+                    continue;
+                }
+                active.insert(
+                    episode_id,
+                    Running {
+                        stop: stop.clone(),
+                        discard: false,
+                    },
+                );
+            }
+            free -= 1;
+
+            let scheduler = self.clone();
+            tokio::spawn(async move {
+                scheduler.run(episode_id, is_auto, stop).await;
+                scheduler.reservations.lock().unwrap().remove(&episode_id);
+                scheduler.active.lock().unwrap().remove(&episode_id);
+                scheduler.wake.notify_one();
+            });
+        }
+    }
+
+    async fn run(&self, episode_id: i32, is_auto: bool, stop: Arc<AtomicBool>) {
+        let (episode, podcast) = match self.db.get_episode(episode_id).await {
+            Ok(Some(episode)) => match self.db.get_podcast(episode.podcast_id).await {
+                Ok(Some(podcast)) => (episode, podcast),
+                _ => {
+                    let _ = self.db.remove_queued_download(episode_id).await;
+                    return;
+                }
+            },
+            _ => {
+                let _ = self.db.remove_queued_download(episode_id).await;
+                return;
+            }
+        };
+
+        if stop.load(Ordering::Relaxed) {
+            self.stopped(episode_id, &podcast, &episode, None);
+            return;
+        }
+        self.set_status(episode_id, DownloadStatus::Downloading, None)
+            .await;
+
+        let dm = self.download_manager.clone();
+        let tx = self.event_tx.clone();
+        let name = self.download_name(&podcast, &episode);
+        let url = episode.url.clone();
+        let stop_flag = stop.clone();
+        let reservations = self.reservations.clone();
+        let (quota, limited) = {
+            let settings = self.settings.read().unwrap();
+            (
+                settings.download_quota_bytes(),
+                is_auto || settings.limit_manual_downloads,
+            )
+        };
+        let result = tokio::task::spawn_blocking(move || {
+            let progress_tx = tx.clone();
+            let mut on_progress = |bytes, total| {
+                let _ = progress_tx.send(AppEvent::DownloadProgress {
+                    episode_id,
+                    bytes,
+                    total,
+                });
+            };
+            let mut reserve = |part: &Path, size: u64| {
+                let Some(quota) = quota else {
+                    return Ok(());
+                };
+                let mut reservations = reservations.lock().unwrap();
+                // Files on disk are counted as they are; other downloads still have the rest
+                // of their claims to write.
+                let others: u64 = reservations
+                    .iter()
+                    .filter(|(id, _)| **id != episode_id)
+                    .map(|(_, r)| r.outstanding())
+                    .sum();
+                let own = Reservation {
+                    part: part.to_path_buf(),
+                    size,
+                };
+                let mut on_evicted = |evicted| {
+                    let _ = tx.send(AppEvent::DownloadStatusChanged {
+                        episode_id: evicted,
+                        status: DownloadStatus::NotDownloaded,
+                        path: None,
+                        reason: None,
+                    });
+                };
+                dm.make_room(
+                    own.outstanding() + others,
+                    quota,
+                    episode_id,
+                    &mut on_evicted,
+                )?;
+                reservations.insert(episode_id, own);
+                Ok(())
+            };
+            dm.download(
+                url,
+                episode_id,
+                &name,
+                DownloadControl {
+                    on_progress: &mut on_progress,
+                    reserve: &mut reserve,
+                    limited,
+                    stop: &stop_flag,
+                },
+            )
+        })
+        .await
+        .unwrap_or_else(|e| Err(DownloadError::Io(e.to_string())));
+
+        match result {
+            // Paused or cancelled; whoever stopped it has already updated the status.
+            Err(_) if stop.load(Ordering::Relaxed) => {
+                self.stopped(episode_id, &podcast, &episode, None);
+            }
+            Ok(path) => {
+                let settings = self.settings.read().unwrap().clone();
+                if settings.write_download_tags {
+                    self.tag(&path, &podcast, &episode).await;
+                }
+                // Cancelled after the last read, or while tagging. A download paused that late
+                // has nothing left to resume, so it is finished like any other.
+                if stop.load(Ordering::Relaxed)
+                    && self.stopped(episode_id, &podcast, &episode, Some(&path))
+                {
+                    return;
+                }
+
+                let path_str = path.to_string_lossy().to_string();
+                let _ = self.db.remove_queued_download(episode_id).await;
+                self.set_status(episode_id, DownloadStatus::Downloaded, Some(path_str))
+                    .await;
+
+                self.apply_retention(podcast.id).await;
+                if settings.notify_download_complete {
+                    crate::notifier::notify_download_complete(&episode.title);
+                }
+                if !is_auto {
+                    let _ = self.event_tx.send(AppEvent::Toast(
+                        crate::components::toast::ToastMessage::success("Download complete"),
+                    ));
+                }
+            }
+            Err(e) => {
+                eprintln!("[downloads] '{}' failed: {e}", episode.title);
+                let attempts = if e.is_permanent() {
+                    // Nothing left to resume; don't let a stale partial file hold quota.
+                    let name = self.download_name(&podcast, &episode);
+                    self.download_manager.discard_partial(&name, episode_id);
+                    MAX_ATTEMPTS
+                } else {
+                    self.db
+                        .record_download_failure(episode_id, e.to_string(), next_attempt_at)
+                        .await
+                        .unwrap_or(MAX_ATTEMPTS)
+                };
+                // Still queued for another try; only the last failure is reported.
+                if attempts < MAX_ATTEMPTS {
+                    self.set_status(episode_id, DownloadStatus::Queued, None)
+                        .await;
+                    return;
+                }
+                let _ = self.db.remove_queued_download(episode_id).await;
+                let _ = self
+                    .db
+                    .update_episode_download_status(episode_id, DownloadStatus::Failed, None)
+                    .await;
+                let _ = self.event_tx.send(AppEvent::DownloadStatusChanged {
+                    episode_id,
+                    status: DownloadStatus::Failed,
+                    path: None,
+                    reason: Some(e.to_string()),
+                });
+            }
+        }
+    }
+
+    // Best effort: a file that can't be tagged is still a good download.
+    async fn tag(&self, path: &Path, podcast: &Podcast, episode: &Episode) {
+        let chapters = match &episode.chapters_url {
+            Some(url) => chapters::fetch_chapters(self.http.clone(), url)
+                .await
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        let dm = self.download_manager.clone();
+        let path = path.to_path_buf();
+        let (podcast, episode) = (podcast.clone(), episode.clone());
+        let result = tokio::task::spawn_blocking(move || {
+            dm.write_tags(&path, &podcast, &episode, chapters)
+                .map_err(|e| format!("could not tag '{}': {e}", episode.title))
+        })
+        .await;
+        if let Ok(Err(e)) = result {
+            eprintln!("[downloads] {e}");
+        }
+    }
+
+    // Cleans up after a download that was stopped, and returns whether it was cancelled. A
+    // cancelled one loses its partial file, or the finished file if it completed just as it
+    // was cancelled. A paused one keeps either. `path` must be a file this download wrote;
+    // `download` never hands back one that was already on disk.
+    fn stopped(
+        &self,
+        episode_id: i32,
+        podcast: &Podcast,
+        episode: &Episode,
+        path: Option<&Path>,
+    ) -> bool {
+        let discard = self
+            .active
+            .lock()
+            .unwrap()
+            .get(&episode_id)
+            .is_some_and(|running| running.discard);
+        if !discard {
+            return false;
+        }
+        match path {
+            Some(path) => {
+                let _ = std::fs::remove_file(path);
+            }
+            None => {
+                let name = self.download_name(podcast, episode);
+                self.download_manager.discard_partial(&name, episode_id);
+            }
+        }
+        true
+    }
+
+    fn download_name(&self, podcast: &Podcast, episode: &Episode) -> DownloadName {
+        let template = self.settings.read().unwrap().download_name_template.clone();
+        DownloadName::new(&template, podcast, episode)
+    }
+
+    // Started and not asked to stop.
+    fn is_running(&self, episode_id: i32) -> bool {
+        self.active
+            .lock()
+            .unwrap()
+            .get(&episode_id)
+            .is_some_and(|running| !running.stop.load(Ordering::Relaxed))
+    }
+
+    fn stop(&self, episode_id: i32) {
+        if let Some(running) = self.active.lock().unwrap().get(&episode_id) {
+            running.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    async fn set_status(&self, episode_id: i32, status: DownloadStatus, path: Option<String>) {
+        let _ = self
+            .db
+            .update_episode_download_status(episode_id, status, path.clone())
+            .await;
+        self.send_status(episode_id, status, path);
+    }
+
+    fn send_status(&self, episode_id: i32, status: DownloadStatus, path: Option<String>) {
+        let _ = self.event_tx.send(AppEvent::DownloadStatusChanged {
+            episode_id,
+            status,
+            path,
+            reason: None,
+        });
+    }
+}
+
+// Minutes since local midnight, for the auto-download window.
+pub fn local_minute_of_day() -> i32 {
+    use chrono::Timelike;
+    let now = chrono::Local::now();
+    (now.hour() * 60 + now.minute()) as i32
+}
+
+// Backoff for the next try after `attempts` failures: 1 min, 4 min, 16 min, ... up to 6 h.
+fn next_attempt_at(attempts: i32) -> i64 {
+    let quadruplings = (attempts - 1).clamp(0, 8) as u32;
+    let delay = (RETRY_BASE_SECS * 4i64.pow(quadruplings)).min(MAX_RETRY_DELAY_SECS);
+    chrono::Utc::now().timestamp() + delay
+}
@@ -0,0 +1,228 @@
+use std::path::Path;
+use std::time::Duration;
+
+use id3::frame::{Chapter as ChapterFrame, Comment, Picture, PictureType, TableOfContents};
+use id3::{Frame, Tag, TagLike, Timestamp, Version};
+
+use crate::chapters::Chapter;
+use crate::utils::string_utils::strip_html;
+
+/// Metadata written into a finished download so it stays identifiable outside RCast.
+pub struct EpisodeTags {
+    pub title: String,
+    pub podcast: String,
+    pub publish_date: i64,
+    pub description: String,
+    // Seconds; 0 when unknown.
+    pub duration: i64,
+    pub artwork: Option<Vec<u8>>,
+    pub chapters: Vec<Chapter>,
+}
+
+enum Format {
+    Id3,
+    Mp4,
+}
+
+fn format_of(path: &Path) -> Option<Format> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    match extension.as_str() {
+        "mp3" => Some(Format::Id3),
+        "m4a" | "m4b" | "mp4" | "m4v" => Some(Format::Mp4),
+        _ => None,
+    }
+}
+
+// MP3 gets ID3 and MP4/M4A gets iTunes-style atoms. Other containers are left untouched.
+pub fn can_tag(path: &Path) -> bool {
+    format_of(path).is_some()
+}
+
+// Writes `tags` in whichever format suits the file.
+pub fn write(path: &Path, tags: &EpisodeTags) -> Result<(), String> {
+    match format_of(path) {
+        Some(Format::Id3) => write_id3(path, tags),
+        Some(Format::Mp4) => write_mp4(path, tags),
+        None => Ok(()),
+    }
+}
+
+/// Writes `tags` into the file's ID3v2 tag.
+///
+/// Frames the publisher already set that we don't manage are kept. Chapters become `CHAP`
+/// frames listed in a single top-level `CTOC`, replacing any chapters in the file.
+fn write_id3(path: &Path, tags: &EpisodeTags) -> Result<(), String> {
+    let mut tag = id3::no_tag_ok(id3::partial_tag_ok(Tag::read_from_path(path)))
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+
+    tag.set_title(&tags.title);
+    tag.set_artist(&tags.podcast);
+    tag.set_album(&tags.podcast);
+    tag.set_genre("Podcast");
+
+    if let Some(date) = chrono::DateTime::from_timestamp(tags.publish_date, 0)
+        && tags.publish_date > 0
+    {
+        use chrono::Datelike;
+        tag.set_date_released(Timestamp {
+            year: date.year(),
+            month: Some(date.month() as u8),
+            day: Some(date.day() as u8),
+            hour: None,
+            minute: None,
+            second: None,
+        });
+    }
+
+    let description = strip_html(&tags.description);
+    if !description.is_empty() {
+        tag.remove_comment(Some(""), None);
+        tag.add_frame(Comment {
+            lang: "eng".to_string(),
+            description: String::new(),
+            text: description,
+        });
+    }
+
+    if let Some((format, data)) = tags.artwork.as_deref().and_then(cover_art) {
+        tag.remove_picture_by_type(PictureType::CoverFront);
+        tag.add_frame(Picture {
+            mime_type: format.mime_type().to_string(),
+            picture_type: PictureType::CoverFront,
+            description: String::new(),
+            data,
+        });
+    }
+
+    if !tags.chapters.is_empty() {
+        tag.remove_all_chapters();
+        tag.remove_all_tables_of_contents();
+        add_chapters(&mut tag, &tags.chapters, tags.duration);
+    }
+
+    tag.write_to_path(path, Version::Id3v24)
+        .map_err(|e| e.to_string())
+}
+
+/// Writes `tags` into the file's MP4 metadata atoms.
+///
+/// Items the publisher already set that we don't manage are kept. Chapters are written as a
+/// Nero `chpl` chapter list, replacing any list in the file; a chapter track, if there is
+/// one, is left alone.
+fn write_mp4(path: &Path, tags: &EpisodeTags) -> Result<(), String> {
+    let mut tag = mp4ameta::Tag::read_from_path(path).map_err(|e| e.to_string())?;
+
+    tag.set_title(&tags.title);
+    tag.set_artist(&tags.podcast);
+    tag.set_album(&tags.podcast);
+    tag.set_genre("Podcast");
+
+    if let Some(date) = chrono::DateTime::from_timestamp(tags.publish_date, 0)
+        && tags.publish_date > 0
+    {
+        tag.set_year(date.format("%Y-%m-%d").to_string());
+    }
+
+    let description = strip_html(&tags.description);
+    if !description.is_empty() {
+        tag.set_description(description);
+    }
+
+    if let Some((format, data)) = tags.artwork.as_deref().and_then(cover_art) {
+        tag.set_artwork(match format {
+            CoverFormat::Jpeg => mp4ameta::Img::jpeg(data),
+            CoverFormat::Png => mp4ameta::Img::png(data),
+        });
+    }
+
+    if !tags.chapters.is_empty() {
+        *tag.chapter_list_mut() = tags
+            .chapters
+            .iter()
+            .map(|chapter| {
+                mp4ameta::Chapter::new(
+                    Duration::from_secs_f64(chapter.start_time.max(0.0)),
+                    chapter.title.clone(),
+                )
+            })
+            .collect();
+    }
+
+    let config = mp4ameta::WriteConfig {
+        write_chapter_track: false,
+        ..mp4ameta::WriteConfig::DEFAULT
+    };
+    tag.write_with_path(path, &config)
+        .map_err(|e| e.to_string())
+}
+
+fn add_chapters(tag: &mut Tag, chapters: &[Chapter], duration: i64) {
+    let duration_ms = (duration.max(0) * 1000) as u32;
+    let mut toc_elements = Vec::new();
+
+    for (i, chapter) in chapters.iter().enumerate() {
+        let element_id = format!("chp{i}");
+        let start_time = (chapter.start_time.max(0.0) * 1000.0) as u32;
+        // Each chapter runs until the next one; the last until the end of the episode.
+        let end_time = chapters
+            .get(i + 1)
+            .map(|next| (next.start_time * 1000.0) as u32)
+            .unwrap_or(duration_ms)
+            .max(start_time);
+
+        if chapter.toc {
+            toc_elements.push(element_id.clone());
+        }
+        tag.add_frame(ChapterFrame {
+            element_id,
+            start_time,
+            end_time,
+            // Byte offsets unknown; players fall back to the times.
+            start_offset: u32::MAX,
+            end_offset: u32::MAX,
+            frames: vec![Frame::text("TIT2", chapter.title.clone())],
+        });
+    }
+
+    tag.add_frame(TableOfContents {
+        element_id: "toc".to_string(),
+        top_level: true,
+        ordered: true,
+        elements: toc_elements,
+        frames: Vec::new(),
+    });
+}
+
+// The image formats both tag types can carry.
+#[derive(Clone, Copy)]
+enum CoverFormat {
+    Jpeg,
+    Png,
+}
+
+impl CoverFormat {
+    fn mime_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+        }
+    }
+}
+
+// Podcast artwork as a JPEG or PNG. Other formats (WebP, GIF, ...) are converted to PNG;
+// None if the data can't be decoded at all.
+fn cover_art(data: &[u8]) -> Option<(CoverFormat, Vec<u8>)> {
+    if data.starts_with(b"\xFF\xD8\xFF") {
+        return Some((CoverFormat::Jpeg, data.to_vec()));
+    }
+    if data.starts_with(b"\x89PNG\r\n\x1A\n") {
+        return Some((CoverFormat::Png, data.to_vec()));
+    }
+    let image = image::load_from_memory(data).ok()?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .ok()?;
+    Some((CoverFormat::Png, png))
+}
@@ -1,3 +1,4 @@
+use crate::ports::HttpError;
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -45,3 +46,78 @@ impl From<rusqlite::Error> for DatabaseError {
         DatabaseError::generic_error(err.to_string())
     }
 }
+
+// Why a download was rejected. Shown to the user as the failure reason.
+#[derive(Debug, Clone)]
+pub enum DownloadError {
+    Status(u16),
+    Network(String),
+    NotMedia(String),
+    Empty,
+    Incomplete { received: u64, expected: u64 },
+    SizeMismatch { received: u64, expected: u64 },
+    Undecodable(String),
+    Io(String),
+    QuotaExceeded { needed: u64, quota: u64 },
+    Stopped,
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::utils::string_utils::format_bytes;
+        match self {
+            Self::Status(code) => write!(f, "Server returned HTTP {code}"),
+            Self::Network(msg) => write!(f, "Network error: {msg}"),
+            Self::NotMedia(mime) => write!(f, "Server sent {mime} instead of audio"),
+            Self::Empty => write!(f, "Server sent an empty file"),
+            Self::Incomplete { received, expected } => write!(
+                f,
+                "Download incomplete ({} of {})",
+                format_bytes(*received as i64),
+                format_bytes(*expected as i64)
+            ),
+            Self::SizeMismatch { received, expected } => write!(
+                f,
+                "Got {} but the server announced {}",
+                format_bytes(*received as i64),
+                format_bytes(*expected as i64)
+            ),
+            Self::Undecodable(msg) => write!(f, "File is not playable audio: {msg}"),
+            Self::Io(msg) => write!(f, "Could not save file: {msg}"),
+            Self::QuotaExceeded { needed, quota } => write!(
+                f,
+                "{} won't fit under the {} storage limit",
+                format_bytes(*needed as i64),
+                format_bytes(*quota as i64)
+            ),
+            Self::Stopped => write!(f, "Download stopped"),
+        }
+    }
+}
+
+impl DownloadError {
+    // Errors that trying again won't fix, so the download isn't retried.
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            self,
+            Self::QuotaExceeded { .. } | Self::NotMedia(_) | Self::Undecodable(_)
+        )
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+impl From<HttpError> for DownloadError {
+    fn from(err: HttpError) -> Self {
+        match err {
+            HttpError::Status(code) => Self::Status(code),
+            other => Self::Network(other.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for DownloadError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
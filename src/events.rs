@@ -12,6 +12,13 @@ pub enum AppEvent {
     PodcastsLoaded(Vec<Podcast>),
     PodcastAdded(Podcast),
     PodcastRemoved(i32),
+    // Title, description or artwork changed upstream during sync.
+    PodcastUpdated(Podcast),
+    // `moved`'s feed now points at a URL that `existing` is already subscribed to.
+    FeedMoveConflict {
+        moved: Box<Podcast>,
+        existing: Box<Podcast>,
+    },
     PodcastDetailLoaded {
         podcast: Podcast,
         episodes: Vec<Episode>,
@@ -28,10 +35,18 @@ pub enum AppEvent {
     },
 
     // Downloads
+    // `reason` explains a Failed status.
     DownloadStatusChanged {
         episode_id: i32,
         status: DownloadStatus,
         path: Option<String>,
+        reason: Option<String>,
+    },
+    // Bytes on disk so far; total is None when the server doesn't report a size.
+    DownloadProgress {
+        episode_id: i32,
+        bytes: u64,
+        total: Option<u64>,
     },
 
     // Queue
@@ -75,6 +90,21 @@ pub enum AppEvent {
     // Statistics
     ListeningStatsLoaded(crate::db::models::ListeningStats),
 
+    // Network audit
+    NetworkAuditLoaded(Vec<crate::db::models::NetworkAuditEntry>),
+
+    // Storage
+    StorageUsageLoaded(Vec<crate::types::PodcastStorage>),
+    DownloadsReconciled(Vec<crate::types::UnknownFile>),
+    // Missing downloads left as they are because the download folder looked unavailable.
+    MissingDownloadsHeld(Vec<i32>),
+    AudioCacheLoaded(crate::types::AudioCacheStats),
+    LibraryMoveProgress {
+        done: usize,
+        total: usize,
+    },
+    LibraryMoveFinished,
+
     // Cross-cutting
     Toast(ToastMessage),
     Error(String),
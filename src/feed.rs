@@ -0,0 +1,530 @@
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use serde::Deserialize;
+
+use crate::db::models::{DownloadStatus, Episode};
+
+// Show-level metadata and episodes as parsed from a feed body.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedFeed {
+    pub title: String,
+    pub description: String,
+    pub image_url: String,
+    pub episodes: Vec<Episode>,
+    // <itunes:new-feed-url> — the publisher has moved the feed here.
+    pub new_feed_url: Option<String>,
+    // <ttl> — how long the feed may be cached before refetching.
+    pub ttl_minutes: Option<i32>,
+    // Declared publishing cadence (sy:updatePeriod/updateFrequency, podcast:updateFrequency).
+    pub update_minutes: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+/// Parses an RSS 2.0, Atom or JSON Feed body into the same shape.
+///
+/// The format is sniffed from the body rather than the Content-Type header, since many
+/// hosts serve every feed as `text/xml` or `application/octet-stream`.
+pub fn parse_feed(body: &str) -> anyhow::Result<ParsedFeed> {
+    let body = body.trim_start_matches('\u{feff}');
+    match detect_format(body) {
+        FeedFormat::Rss => parse_rss(body),
+        FeedFormat::Atom => parse_atom(body),
+        FeedFormat::Json => parse_json_feed(body),
+    }
+}
+
+fn detect_format(body: &str) -> FeedFormat {
+    if body.trim_start().starts_with('{') {
+        return FeedFormat::Json;
+    }
+
+    // The root element decides between RSS (<rss>, <rdf:RDF>) and Atom (<feed>).
+    let mut reader = Reader::from_str(body);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                return if local_name(&e) == "feed" {
+                    FeedFormat::Atom
+                } else {
+                    FeedFormat::Rss
+                };
+            }
+            Ok(Event::Eof) | Err(_) => return FeedFormat::Rss,
+            _ => {}
+        }
+    }
+}
+
+// -- RSS 2.0 --------------------------------------------------------------------
+
+fn parse_rss(body: &str) -> anyhow::Result<ParsedFeed> {
+    let channel = rss::Channel::read_from(body.as_bytes())?;
+
+    let title = channel.title().to_string();
+    let description = channel.description().to_string();
+    let image_url = channel
+        .image()
+        .map(|i| i.url().to_string())
+        .or_else(|| {
+            channel
+                .itunes_ext()
+                .and_then(|e| e.image())
+                .map(|u| u.to_string())
+        })
+        .unwrap_or_default();
+
+    let new_feed_url = channel
+        .itunes_ext()
+        .and_then(|e| e.new_feed_url())
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty());
+
+    let ttl_minutes = channel
+        .ttl()
+        .and_then(|t| t.trim().parse::<i32>().ok())
+        .filter(|t| *t > 0);
+
+    // podcast:updateFrequency is podcast-specific, so it wins over the generic sy: module.
+    let podcast_frequency = channel
+        .extensions()
+        .get("podcast")
+        .and_then(|ns| ns.get("updateFrequency"))
+        .and_then(|v| v.first())
+        .and_then(|ext| ext.attrs().get("rrule"))
+        .and_then(|rrule| rrule_minutes(rrule));
+    let syndication_frequency = channel.syndication_ext().map(|sy| {
+        use rss::extension::syndication::UpdatePeriod;
+        let period = match sy.period() {
+            UpdatePeriod::Hourly => 60,
+            UpdatePeriod::Daily => 24 * 60,
+            UpdatePeriod::Weekly => 7 * 24 * 60,
+            UpdatePeriod::Monthly => 30 * 24 * 60,
+            UpdatePeriod::Yearly => 365 * 24 * 60,
+        };
+        period / sy.frequency().max(1) as i32
+    });
+    let update_minutes = podcast_frequency.or(syndication_frequency);
+
+    let episodes = channel
+        .items()
+        .iter()
+        .filter_map(|item| {
+            let enclosure = item.enclosure()?;
+
+            let duration = item
+                .itunes_ext()
+                .and_then(|ext| ext.duration())
+                .and_then(parse_duration)
+                .unwrap_or(0);
+
+            let chapters_url = item
+                .extensions()
+                .get("podcast")
+                .and_then(|ns| ns.get("chapters"))
+                .and_then(|v| v.first())
+                .and_then(|ext| ext.attrs().get("url"))
+                .cloned();
+
+            let publish_date = item
+                .pub_date()
+                .and_then(|d| chrono::DateTime::parse_from_rfc2822(d).ok())
+                .map(|d| d.timestamp())
+                .unwrap_or(0);
+
+            let guid = item
+                .guid()
+                .map(|g| g.value().trim().to_string())
+                .filter(|g| !g.is_empty());
+
+            let season = item
+                .itunes_ext()
+                .and_then(|ext| ext.season())
+                .and_then(|s| s.trim().parse().ok());
+            let episode_number = item
+                .itunes_ext()
+                .and_then(|ext| ext.episode())
+                .and_then(|e| e.trim().parse().ok());
+
+            Some(Episode {
+                guid,
+                season,
+                episode_number,
+                title: item.title().unwrap_or("Untitled").to_string(),
+                description: item.description().unwrap_or("").to_string(),
+                url: enclosure.url().to_string(),
+                audio_type: enclosure.mime_type().to_string(),
+                publish_date,
+                duration,
+                chapters_url,
+                ..empty_episode()
+            })
+        })
+        .collect();
+
+    Ok(ParsedFeed {
+        title,
+        description,
+        image_url,
+        episodes,
+        new_feed_url,
+        ttl_minutes,
+        update_minutes,
+    })
+}
+
+// -- Atom -----------------------------------------------------------------------
+
+#[derive(Default)]
+struct AtomEntry {
+    id: String,
+    title: String,
+    summary: String,
+    content: String,
+    published: String,
+    updated: String,
+    duration: String,
+    enclosure: Option<(String, String)>,
+}
+
+fn parse_atom(body: &str) -> anyhow::Result<ParsedFeed> {
+    let mut reader = Reader::from_str(body);
+
+    let mut title = String::new();
+    let mut subtitle = String::new();
+    let mut icon = String::new();
+    let mut logo = String::new();
+    let mut itunes_image = String::new();
+    let mut new_feed_url = String::new();
+
+    let mut entries: Vec<AtomEntry> = Vec::new();
+    let mut entry: Option<AtomEntry> = None;
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let name = local_name(&e);
+                match name.as_str() {
+                    "entry" if path.len() == 1 => entry = Some(AtomEntry::default()),
+                    "link" => read_atom_link(&e, &reader, entry.as_mut()),
+                    _ => {}
+                }
+                // Text is collected from a field's start to its end, through any nested
+                // markup (`<content type="xhtml">` wraps its text in <div>, <p>, ...).
+                if path.len() == 1 || (path.len() == 2 && entry.is_some()) {
+                    text.clear();
+                }
+                path.push(name);
+            }
+            Event::Empty(e) => match local_name(&e).as_str() {
+                "link" => read_atom_link(&e, &reader, entry.as_mut()),
+                // <itunes:image href="..."/> is common on podcast Atom feeds.
+                "image" if path.len() == 1 => {
+                    if let Some(href) = attribute(&e, &reader, "href") {
+                        itunes_image = href;
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(t) => text.push_str(&t.xml10_content()?),
+            Event::CData(c) => text.push_str(&c.decode()?),
+            Event::GeneralRef(r) => {
+                let reference = format!("&{};", r.decode()?);
+                match quick_xml::escape::unescape(&reference) {
+                    Ok(resolved) => text.push_str(&resolved),
+                    Err(_) => text.push_str(&reference),
+                }
+            }
+            Event::End(_) => {
+                let name = path.pop().unwrap_or_default();
+                if path.len() > 2 || (path.len() == 2 && entry.is_none()) {
+                    // Keep block-level elements from running together.
+                    if matches!(name.as_str(), "p" | "div" | "li" | "br") {
+                        text.push('\n');
+                    }
+                    continue;
+                }
+                let value = std::mem::take(&mut text).trim().to_string();
+
+                // path now holds the parent chain: [feed] for feed-level children,
+                // [feed, entry] for entry-level children.
+                match (path.len(), entry.as_mut()) {
+                    (1, Some(_)) if name == "entry" => entries.extend(entry.take()),
+                    (2, Some(en)) => match name.as_str() {
+                        "id" => en.id = value,
+                        "title" => en.title = value,
+                        "summary" => en.summary = value,
+                        "content" => en.content = value,
+                        "published" => en.published = value,
+                        "updated" => en.updated = value,
+                        "duration" => en.duration = value,
+                        _ => {}
+                    },
+                    (1, None) => match name.as_str() {
+                        "title" => title = value,
+                        "subtitle" => subtitle = value,
+                        "icon" => icon = value,
+                        "logo" => logo = value,
+                        "new-feed-url" => new_feed_url = value,
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let image_url = [itunes_image, logo, icon]
+        .into_iter()
+        .find(|u| !u.is_empty())
+        .unwrap_or_default();
+
+    let episodes = entries
+        .into_iter()
+        .filter_map(|en| {
+            let (url, mime_type) = en.enclosure?;
+            let publish_date = [en.published.as_str(), en.updated.as_str()]
+                .into_iter()
+                .find_map(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                .map(|d| d.timestamp())
+                .unwrap_or(0);
+            let description = if en.content.is_empty() { en.summary } else { en.content };
+            let title = if en.title.is_empty() { "Untitled".to_string() } else { en.title };
+
+            Some(Episode {
+                guid: Some(en.id).filter(|id| !id.is_empty()),
+                title,
+                description,
+                url,
+                audio_type: mime_type,
+                publish_date,
+                duration: parse_duration(&en.duration).unwrap_or(0),
+                ..empty_episode()
+            })
+        })
+        .collect();
+
+    Ok(ParsedFeed {
+        title,
+        description: subtitle,
+        image_url,
+        episodes,
+        new_feed_url: Some(new_feed_url).filter(|u| !u.is_empty()),
+        ..Default::default()
+    })
+}
+
+// Records the first audio/video `<link rel="enclosure">` of an entry.
+fn read_atom_link(e: &BytesStart, reader: &Reader<&[u8]>, entry: Option<&mut AtomEntry>) {
+    let Some(entry) = entry else { return };
+    if entry.enclosure.is_some() || attribute(e, reader, "rel").as_deref() != Some("enclosure") {
+        return;
+    }
+    let Some(href) = attribute(e, reader, "href") else { return };
+    let mime_type = attribute(e, reader, "type").unwrap_or_default();
+
+    if mime_type.is_empty() || mime_type.starts_with("audio/") || mime_type.starts_with("video/") {
+        entry.enclosure = Some((href, mime_type));
+    }
+}
+
+// -- JSON Feed 1.1 --------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct JsonFeed {
+    title: Option<String>,
+    description: Option<String>,
+    icon: Option<String>,
+    favicon: Option<String>,
+    #[serde(default)]
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedItem {
+    // The spec requires a string, but numeric ids are common in the wild.
+    id: Option<serde_json::Value>,
+    title: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    #[serde(default)]
+    attachments: Vec<JsonFeedAttachment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonFeedAttachment {
+    url: String,
+    mime_type: Option<String>,
+    duration_in_seconds: Option<f64>,
+}
+
+fn parse_json_feed(body: &str) -> anyhow::Result<ParsedFeed> {
+    let feed: JsonFeed = serde_json::from_str(body)?;
+
+    let image_url = feed.icon.or(feed.favicon).unwrap_or_default();
+
+    let episodes = feed
+        .items
+        .into_iter()
+        .filter_map(|item| {
+            // Same rule as Atom enclosures: an untyped attachment or an audio/video one.
+            let is_media = |a: &&JsonFeedAttachment| {
+                a.mime_type.as_deref().is_none_or(|m| {
+                    m.is_empty() || m.starts_with("audio/") || m.starts_with("video/")
+                })
+            };
+            let attachment = item.attachments.iter().find(is_media)?;
+
+            let publish_date = item
+                .date_published
+                .as_deref()
+                .or(item.date_modified.as_deref())
+                .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                .map(|d| d.timestamp())
+                .unwrap_or(0);
+
+            let description = item
+                .content_html
+                .as_deref()
+                .or(item.content_text.as_deref())
+                .or(item.summary.as_deref())
+                .unwrap_or("");
+
+            let guid = match &item.id {
+                Some(serde_json::Value::String(id)) => Some(id.trim().to_string()),
+                Some(serde_json::Value::Number(id)) => Some(id.to_string()),
+                _ => None,
+            }
+            .filter(|id| !id.is_empty());
+
+            Some(Episode {
+                guid,
+                title: item.title.clone().unwrap_or_else(|| "Untitled".to_string()),
+                description: description.to_string(),
+                url: attachment.url.clone(),
+                audio_type: attachment.mime_type.clone().unwrap_or_default(),
+                publish_date,
+                duration: attachment.duration_in_seconds.map(|d| d as i64).unwrap_or(0),
+                ..empty_episode()
+            })
+        })
+        .collect();
+
+    Ok(ParsedFeed {
+        title: feed.title.unwrap_or_default(),
+        description: feed.description.unwrap_or_default(),
+        image_url,
+        episodes,
+        ..Default::default()
+    })
+}
+
+// -- Shared helpers -------------------------------------------------------------
+
+fn empty_episode() -> Episode {
+    let now = chrono::Utc::now().timestamp();
+    Episode {
+        id: 0,
+        podcast_id: 0,
+        guid: None,
+        title: String::new(),
+        description: String::new(),
+        url: String::new(),
+        audio_type: String::new(),
+        publish_date: 0,
+        is_played: false,
+        played_at: None,
+        duration: 0,
+        position_seconds: 0.0,
+        created_at: now,
+        updated_at: now,
+        download_status: DownloadStatus::NotDownloaded,
+        downloaded_path: None,
+        downloaded_at: None,
+        speed_preset: None,
+        chapters_url: None,
+        total_listen_seconds: 0,
+        season: None,
+        episode_number: None,
+        is_protected: false,
+    }
+}
+
+// Parses `HH:MM:SS`, `MM:SS` or plain seconds.
+fn parse_duration(d: &str) -> Option<i64> {
+    let parts: Vec<&str> = d.trim().split(':').collect();
+    match parts.len() {
+        3 => {
+            let h: i64 = parts[0].parse().ok()?;
+            let m: i64 = parts[1].parse().ok()?;
+            let s: i64 = parts[2].parse().ok()?;
+            Some(h * 3600 + m * 60 + s)
+        }
+        2 => {
+            let m: i64 = parts[0].parse().ok()?;
+            let s: i64 = parts[1].parse().ok()?;
+            Some(m * 60 + s)
+        }
+        1 => parts[0].parse().ok(),
+        _ => None,
+    }
+}
+
+// Approximates an iCalendar RRULE (`FREQ=WEEKLY;INTERVAL=2`) as minutes between releases.
+fn rrule_minutes(rrule: &str) -> Option<i32> {
+    let mut freq = None;
+    let mut interval = 1;
+    let mut per_period = 1;
+    for part in rrule.trim().trim_start_matches("RRULE:").split(';') {
+        match part.split_once('=') {
+            Some(("FREQ", f)) => {
+                freq = Some(match f {
+                    "MINUTELY" => 1,
+                    "HOURLY" => 60,
+                    "DAILY" => 24 * 60,
+                    "WEEKLY" => 7 * 24 * 60,
+                    "MONTHLY" => 30 * 24 * 60,
+                    "YEARLY" => 365 * 24 * 60,
+                    _ => return None,
+                })
+            }
+            Some(("INTERVAL", i)) => interval = i.parse::<i32>().ok()?.max(1),
+            // FREQ=WEEKLY;BYDAY=MO,WE,FR releases three times per period.
+            Some(("BYDAY", days)) => per_period = days.split(',').count().max(1) as i32,
+            _ => {}
+        }
+    }
+    // Feed-supplied intervals can be huge; keep the cadence between a minute and a year.
+    let minutes = freq?.checked_mul(interval)? / per_period;
+    Some(minutes.clamp(1, 365 * 24 * 60))
+}
+
+fn local_name(e: &BytesStart) -> String {
+    std::str::from_utf8(e.local_name().as_ref())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+fn attribute(e: &BytesStart, reader: &Reader<&[u8]>, key: &str) -> Option<String> {
+    e.attributes().flatten().find_map(|attr| {
+        let name = std::str::from_utf8(attr.key.local_name().as_ref()).ok()?.to_lowercase();
+        if name != key {
+            return None;
+        }
+        attr.decoded_and_normalized_value(quick_xml::XmlVersion::Implicit1_0, reader.decoder())
+            .ok()
+            .map(|v| v.trim().to_string())
+    })
+}
@@ -0,0 +1,367 @@
+use std::collections::VecDeque;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::download_manager::parse_content_range;
+use crate::ports::{HttpError, HttpFetcher, HttpRequest, HttpResponse, RequestPurpose};
+use crate::tracking_prefixes::{self, TrackingPrefixes};
+
+// How far the fetch runs ahead of the playhead before it waits.
+const READ_AHEAD: u64 = 8 * 1024 * 1024;
+// Already played audio kept for short seeks backwards.
+const KEEP_BEHIND: u64 = 2 * 1024 * 1024;
+// A seek at most this far past the buffer waits for the data instead of reconnecting.
+const SEEK_GAP: u64 = 256 * 1024;
+const CHUNK_SIZE: usize = 64 * 1024;
+// Reconnects in a row without new data before the stream gives up.
+const MAX_RETRIES: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// An episode played straight off the network.
+///
+/// A background thread fetches the file with HTTP `Range` requests into a window around the
+/// read position: at most `READ_AHEAD` bytes ahead and `KEEP_BEHIND` bytes behind, so memory
+/// stays bounded however long the episode is. Seeking outside the window drops it and starts a
+/// new request at the new position. Servers that ignore `Range` still work, but such a seek
+/// then has to read through the file from the start.
+pub struct HttpStream {
+    shared: Arc<Shared>,
+    pos: u64,
+    len: Option<u64>,
+    content_type: Option<String>,
+}
+
+struct Shared {
+    window: Mutex<Window>,
+    // Signalled whenever data arrives, the reader moves, or the stream is dropped.
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct Window {
+    // Offset of `data[0]` in the file.
+    start: u64,
+    data: VecDeque<u8>,
+    // Read position, which the fetch stays `READ_AHEAD` bytes ahead of.
+    pos: u64,
+    // Bumped on every seek that needs a new request; the fetch drops older responses.
+    generation: u64,
+    // The fetch reached the end of the file.
+    done: bool,
+    error: Option<String>,
+    closed: bool,
+}
+
+impl Window {
+    fn end(&self) -> u64 {
+        self.start + self.data.len() as u64
+    }
+
+    fn restart_at(&mut self, pos: u64) {
+        self.generation += 1;
+        self.start = pos;
+        self.data.clear();
+        self.done = false;
+        self.error = None;
+    }
+}
+
+impl HttpStream {
+    // Blocks until the server has answered, so a bad URL fails here rather than mid-playback.
+    pub fn open(
+        http: Arc<dyn HttpFetcher>,
+        url: String,
+        prefixes: Option<TrackingPrefixes>,
+    ) -> Result<Self, HttpError> {
+        let (response, skip) = request_from(http.as_ref(), &url, prefixes.as_ref(), 0)?;
+        let len = total_len(&response);
+        let content_type = response
+            .header("content-type")
+            .map(|v| v.split(';').next().unwrap_or(v).trim().to_string());
+
+        let shared = Arc::new(Shared {
+            window: Mutex::new(Window::default()),
+            changed: Condvar::new(),
+        });
+        let fetch = Fetch {
+            http,
+            url,
+            prefixes,
+            shared: shared.clone(),
+            len,
+        };
+        thread::spawn(move || fetch.run(response, skip));
+
+        Ok(Self {
+            shared,
+            pos: 0,
+            len,
+            content_type,
+        })
+    }
+
+    // Size of the whole file, if the server said.
+    pub fn len(&self) -> Option<u64> {
+        self.len
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+}
+
+impl Read for HttpStream {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if out.is_empty() || self.len.is_some_and(|len| self.pos >= len) {
+            return Ok(0);
+        }
+
+        let mut window = self.shared.window.lock().unwrap();
+        window.pos = self.pos;
+        loop {
+            let end = window.end();
+            if self.pos >= window.start && self.pos < end {
+                let offset = (self.pos - window.start) as usize;
+                let n = out.len().min(window.data.len() - offset);
+                for (dst, src) in out.iter_mut().zip(window.data.range(offset..offset + n)) {
+                    *dst = *src;
+                }
+                self.pos += n as u64;
+                window.pos = self.pos;
+
+                let keep_from = self.pos.saturating_sub(KEEP_BEHIND);
+                if keep_from > window.start {
+                    let drop = ((keep_from - window.start) as usize).min(window.data.len());
+                    window.data.drain(..drop);
+                    window.start += drop as u64;
+                }
+                self.shared.changed.notify_all();
+                return Ok(n);
+            }
+
+            if self.pos < window.start || self.pos > end + SEEK_GAP {
+                window.restart_at(self.pos);
+                self.shared.changed.notify_all();
+            } else if let Some(e) = &window.error {
+                return Err(io::Error::other(e.clone()));
+            } else if window.done {
+                return Ok(0);
+            }
+            window = self.shared.changed.wait(window).unwrap();
+        }
+    }
+}
+
+impl Seek for HttpStream {
+    // Only moves the position; the next read fetches whatever it needs.
+    fn seek(&mut self, from: SeekFrom) -> io::Result<u64> {
+        let pos = match from {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+            SeekFrom::End(delta) => match self.len {
+                Some(len) => len.checked_add_signed(delta),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "stream length unknown",
+                    ));
+                }
+            },
+        };
+        self.pos = pos.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "seek before start of stream")
+        })?;
+        Ok(self.pos)
+    }
+}
+
+impl Drop for HttpStream {
+    fn drop(&mut self) {
+        self.shared.window.lock().unwrap().closed = true;
+        self.shared.changed.notify_all();
+    }
+}
+
+// The background half of an `HttpStream`.
+struct Fetch {
+    http: Arc<dyn HttpFetcher>,
+    url: String,
+    prefixes: Option<TrackingPrefixes>,
+    shared: Arc<Shared>,
+    len: Option<u64>,
+}
+
+enum Fetched {
+    // The response ended.
+    Eof,
+    // A seek or the stream being dropped made the response useless.
+    Stopped,
+}
+
+impl Fetch {
+    fn run(self, first: HttpResponse, skip: u64) {
+        let mut response = Some((first, skip));
+        let mut failures = 0;
+        loop {
+            let (generation, from) = {
+                let window = self.shared.window.lock().unwrap();
+                if window.closed {
+                    return;
+                }
+                (window.generation, window.end())
+            };
+
+            let result = match response.take() {
+                Some(open) => Ok(open),
+                None => request_from(self.http.as_ref(), &self.url, self.prefixes.as_ref(), from),
+            };
+            let result = result
+                .map_err(|e| e.to_string())
+                .and_then(|(response, skip)| {
+                    self.pump(response, skip, generation)
+                        .map_err(|e| e.to_string())
+                });
+
+            let error = match result {
+                Ok(Fetched::Stopped) => {
+                    failures = 0;
+                    continue;
+                }
+                Ok(Fetched::Eof) => {
+                    let mut window = self.shared.window.lock().unwrap();
+                    if window.generation != generation {
+                        continue;
+                    }
+                    // A connection that closed early is retried from where it stopped.
+                    if self.len.is_none_or(|len| window.end() >= len) {
+                        window.done = true;
+                        self.shared.changed.notify_all();
+                        drop(window);
+                        if !self.wait_for_seek(generation) {
+                            return;
+                        }
+                        failures = 0;
+                        continue;
+                    }
+                    "connection closed early".to_string()
+                }
+                Err(e) => e,
+            };
+
+            {
+                let window = self.shared.window.lock().unwrap();
+                if window.generation != generation {
+                    failures = 0;
+                    continue;
+                }
+                // Only failures that made no progress at all count towards giving up.
+                if window.end() > from {
+                    failures = 0;
+                }
+            }
+            failures += 1;
+            if failures < MAX_RETRIES {
+                thread::sleep(RETRY_DELAY);
+                continue;
+            }
+
+            eprintln!("[stream] giving up on {}: {error}", self.url);
+            {
+                let mut window = self.shared.window.lock().unwrap();
+                window.error = Some(error);
+                self.shared.changed.notify_all();
+            }
+            if !self.wait_for_seek(generation) {
+                return;
+            }
+            failures = 0;
+        }
+    }
+
+    // Copies the response into the window, waiting whenever the window is full.
+    fn pump(
+        &self,
+        mut response: HttpResponse,
+        mut skip: u64,
+        generation: u64,
+    ) -> io::Result<Fetched> {
+        let mut chunk = vec![0u8; CHUNK_SIZE];
+        loop {
+            {
+                let mut window = self.shared.window.lock().unwrap();
+                loop {
+                    if window.closed || window.generation != generation {
+                        return Ok(Fetched::Stopped);
+                    }
+                    if window.end() < window.pos + READ_AHEAD {
+                        break;
+                    }
+                    window = self.shared.changed.wait(window).unwrap();
+                }
+            }
+
+            let n = response.body.read(&mut chunk)?;
+            if n == 0 {
+                return Ok(Fetched::Eof);
+            }
+
+            // The server sent the file from an earlier offset than asked for.
+            let skipped = skip.min(n as u64) as usize;
+            skip -= skipped as u64;
+            if skipped == n {
+                continue;
+            }
+
+            let mut window = self.shared.window.lock().unwrap();
+            if window.generation != generation {
+                return Ok(Fetched::Stopped);
+            }
+            window.data.extend(&chunk[skipped..n]);
+            self.shared.changed.notify_all();
+        }
+    }
+
+    // Waits until the reader seeks elsewhere. False once the stream has been dropped.
+    fn wait_for_seek(&self, generation: u64) -> bool {
+        let mut window = self.shared.window.lock().unwrap();
+        while !window.closed && window.generation == generation {
+            window = self.shared.changed.wait(window).unwrap();
+        }
+        !window.closed
+    }
+}
+
+// Requests the file from byte `from`, returning the response and how many bytes of it to
+// discard to get there (non-zero only when the server ignored the range).
+fn request_from(
+    http: &dyn HttpFetcher,
+    url: &str,
+    prefixes: Option<&TrackingPrefixes>,
+    from: u64,
+) -> Result<(HttpResponse, u64), HttpError> {
+    let request =
+        HttpRequest::get(RequestPurpose::Stream, url).header("Range", format!("bytes={from}-"));
+    let response = tracking_prefixes::get_direct(http, request, prefixes)?;
+
+    if response.status == 206 {
+        match parse_content_range(response.header("content-range")) {
+            Some((start, _)) if start <= from => Ok((response, from - start)),
+            _ => Err(HttpError::Other("server sent the wrong range".to_string())),
+        }
+    } else {
+        Ok((response, from))
+    }
+}
+
+// Only meaningful for the response to a request starting at byte 0.
+pub fn total_len(response: &HttpResponse) -> Option<u64> {
+    if response.status == 206 {
+        parse_content_range(response.header("content-range")).and_then(|(_, total)| total)
+    } else {
+        response
+            .header("content-length")
+            .and_then(|v| v.trim().parse().ok())
+    }
+}
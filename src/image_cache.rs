@@ -1,3 +1,4 @@
+use crate::ports::{HttpFetcher, HttpRequest, RequestPurpose};
 use dirs::data_local_dir;
 use egui::{ColorImage, TextureHandle};
 use std::collections::{HashMap, VecDeque};
@@ -35,34 +36,41 @@ impl TextureStore {
         self.map.insert(key.clone(), texture);
         self.order.push_front(key);
     }
+
+    fn remove(&mut self, key: &str) {
+        self.map.remove(key);
+        self.order.retain(|k| k != key);
+    }
 }
 
 pub struct ImageCache {
     cache_dir: PathBuf,
     textures: Arc<Mutex<TextureStore>>,
+    http: Option<Arc<dyn HttpFetcher>>,
 }
 
 impl ImageCache {
     pub fn new() -> Self {
-        let path = data_local_dir()
-            .unwrap()
-            .join("rcast")
-            .join("cache")
-            .join("images");
+        let path = image_cache_dir();
         create_dir_all(path.clone()).unwrap();
 
         Self {
             cache_dir: path,
             textures: Arc::new(Mutex::new(TextureStore::new(75))),
+            http: None,
         }
     }
 
+    pub fn set_http_fetcher(&mut self, http: Arc<dyn HttpFetcher>) {
+        self.http = Some(http);
+    }
+
     pub fn get_or_load(&self, url: &str, ctx: &egui::Context) -> Option<TextureHandle> {
         if url.is_empty() {
             return None;
         }
         let parsed_url = Self::strip_query(url).ok()?;
-        let cache_key = self.url_to_cache_key(parsed_url.as_str());
+        let cache_key = Self::url_to_cache_key(parsed_url.as_str());
         {
             let textures = self.textures.lock().unwrap();
             if let Some(texture) = textures.get(&cache_key) {
@@ -80,7 +88,33 @@ impl ImageCache {
         self.download_and_cache(url, ctx, &cache_key)
     }
 
-    fn url_to_cache_key(&self, url: &str) -> String {
+    // Raw image bytes for `url` from the disk cache, fetched and cached on a miss. Unlike
+    // `get_or_load` this needs no egui context, so background tasks can use it.
+    pub fn load_bytes(http: &dyn HttpFetcher, url: &str) -> Option<Vec<u8>> {
+        let parsed_url = Self::strip_query(url).ok()?;
+        let cached_path = image_cache_dir().join(Self::url_to_cache_key(parsed_url.as_str()));
+        if let Ok(bytes) = std::fs::read(&cached_path) {
+            return Some(bytes);
+        }
+
+        let response = http.get(HttpRequest::get(RequestPurpose::Image, url)).ok()?;
+        let bytes = response.error_for_status().ok()?.bytes().ok()?;
+        let _ = create_dir_all(image_cache_dir());
+        let _ = std::fs::write(&cached_path, &bytes);
+        Some(bytes)
+    }
+
+    // Drops the texture and disk copy for `url` so the next `get_or_load` fetches it again.
+    pub fn invalidate(&self, url: &str) {
+        let Ok(parsed_url) = Self::strip_query(url) else {
+            return;
+        };
+        let cache_key = Self::url_to_cache_key(parsed_url.as_str());
+        self.textures.lock().unwrap().remove(&cache_key);
+        let _ = std::fs::remove_file(self.cache_dir.join(&cache_key));
+    }
+
+    fn url_to_cache_key(url: &str) -> String {
         let hash = format!("{:x}", md5::compute(url.as_bytes()));
         let extension = url.rsplit('.').next().unwrap_or("jpg");
         format!("{}.{}", hash, extension)
@@ -92,8 +126,8 @@ impl ImageCache {
         ctx: &egui::Context,
         cache_key: &str,
     ) -> Option<TextureHandle> {
-        let response = reqwest::blocking::get(url).ok()?;
-        let bytes = response.bytes().ok()?;
+        let response = self.http.as_ref()?.get(HttpRequest::get(RequestPurpose::Image, url)).ok()?;
+        let bytes = response.error_for_status().ok()?.bytes().ok()?;
 
         let cached_path = self.cache_dir.join(cache_key);
         std::fs::write(&cached_path, &bytes).ok()?;
@@ -174,3 +208,11 @@ impl ImageCache {
         Ok(url)
     }
 }
+
+fn image_cache_dir() -> PathBuf {
+    data_local_dir()
+        .unwrap()
+        .join("rcast")
+        .join("cache")
+        .join("images")
+}
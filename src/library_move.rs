@@ -0,0 +1,224 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::db::Database;
+use crate::db::models::{DownloadStatus, LibraryMoveEntry, LibraryMoveState};
+use crate::events::AppEvent;
+
+// Bytes compared at a time when verifying a copy.
+const COMPARE_CHUNK: usize = 256 * 1024;
+
+#[derive(Debug, Default)]
+pub struct MoveSummary {
+    pub moved: usize,
+    // Files left where they were because they could not be copied.
+    pub failed: usize,
+}
+
+/// Moves every download under `from` to the same place under `to`.
+///
+/// The move is journalled in the database, so if the app is closed halfway it carries on with
+/// `resume_library_move` at the next start. Each file is hard-linked into place when both
+/// folders are on the same filesystem, and otherwise copied and compared byte for byte. The
+/// episodes are switched to their new paths in a single transaction, and only after that are
+/// the old files deleted, so every episode points at a complete file throughout.
+pub async fn move_library(
+    db: &Database,
+    from: &str,
+    to: &str,
+    tx: &UnboundedSender<AppEvent>,
+) -> anyhow::Result<MoveSummary> {
+    let from_root = PathBuf::from(from);
+    let to_root = PathBuf::from(to);
+    if from_root == to_root {
+        return Ok(MoveSummary::default());
+    }
+
+    let scan_db = db.clone();
+    let files = tokio::task::spawn_blocking(move || scan_db.get_downloaded_files_sync()).await??;
+    let entries: Vec<LibraryMoveEntry> = files
+        .into_iter()
+        .filter_map(|file| {
+            let relative = Path::new(&file.path).strip_prefix(&from_root).ok()?;
+            Some(LibraryMoveEntry {
+                episode_id: file.episode_id,
+                from_root: from.to_string(),
+                to_path: to_root.join(relative).to_string_lossy().to_string(),
+                from_path: file.path,
+                state: LibraryMoveState::Pending,
+            })
+        })
+        .collect();
+    if entries.is_empty() {
+        return Ok(MoveSummary::default());
+    }
+
+    db.start_library_move(entries).await?;
+    resume_library_move(db, tx).await
+}
+
+// Finishes a move that was started earlier, if there is one.
+pub async fn resume_library_move(
+    db: &Database,
+    tx: &UnboundedSender<AppEvent>,
+) -> anyhow::Result<MoveSummary> {
+    let mut summary = MoveSummary::default();
+    let entries = db.get_library_move().await?;
+    let total = entries.len();
+    if total == 0 {
+        return Ok(summary);
+    }
+
+    for (done, entry) in entries.iter().enumerate() {
+        if entry.state == LibraryMoveState::Pending {
+            let from = PathBuf::from(&entry.from_path);
+            let to = PathBuf::from(&entry.to_path);
+            match tokio::task::spawn_blocking(move || copy_verified(&from, &to)).await? {
+                Ok(path) => {
+                    db.mark_library_move_copied(
+                        entry.episode_id,
+                        path.to_string_lossy().to_string(),
+                    )
+                    .await?;
+                }
+                Err(e) => {
+                    eprintln!("[library] could not move '{}': {e}", entry.from_path);
+                    summary.failed += 1;
+                }
+            }
+        }
+        let _ = tx.send(AppEvent::LibraryMoveProgress {
+            done: done + 1,
+            total,
+        });
+    }
+
+    // Episodes deleted or re-downloaded during the move keep what they have now.
+    for stale in db.commit_library_move().await? {
+        let _ = fs::remove_file(&stale.to_path);
+    }
+
+    for entry in db.get_library_move().await? {
+        remove_old_file(&entry);
+        db.finish_library_move_entry(entry.episode_id).await?;
+        summary.moved += 1;
+        let _ = tx.send(AppEvent::DownloadStatusChanged {
+            episode_id: entry.episode_id,
+            status: DownloadStatus::Downloaded,
+            path: Some(entry.to_path),
+            reason: None,
+        });
+    }
+    Ok(summary)
+}
+
+// Puts a verified copy of `from` at `to`, or next to it if `to` holds a different file, and
+// returns where it ended up.
+fn copy_verified(from: &Path, to: &Path) -> Result<PathBuf, String> {
+    let dir = to.parent().ok_or("destination has no folder")?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+
+    let mut target = to.to_path_buf();
+    let mut n = 2;
+    while target.exists() {
+        // Copied by an earlier, interrupted attempt.
+        if same_contents(from, &target).unwrap_or(false) {
+            return Ok(target);
+        }
+        target = numbered(to, n);
+        n += 1;
+    }
+
+    if fs::hard_link(from, &target).is_ok() {
+        return Ok(target);
+    }
+
+    // Copy under a temporary name so a half-written file is never mistaken for a finished one.
+    let file_name = target.file_name().unwrap_or_default().to_string_lossy();
+    let part = target.with_file_name(format!("{file_name}.part"));
+    fs::copy(from, &part).map_err(|e| e.to_string())?;
+    let verified = File::open(&part)
+        .and_then(|f| f.sync_all())
+        .and_then(|_| same_contents(from, &part));
+    match verified {
+        Ok(true) => {}
+        Ok(false) => {
+            let _ = fs::remove_file(&part);
+            return Err("copy differs from the original".to_string());
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&part);
+            return Err(e.to_string());
+        }
+    }
+    fs::rename(&part, &target).map_err(|e| e.to_string())?;
+    Ok(target)
+}
+
+// Deletes the original of a committed entry, then any folders that leaves empty inside the
+// old download directory.
+fn remove_old_file(entry: &LibraryMoveEntry) {
+    let from = Path::new(&entry.from_path);
+    if from == Path::new(&entry.to_path) {
+        return;
+    }
+    if let Err(e) = fs::remove_file(from)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        eprintln!("[library] could not delete '{}': {e}", entry.from_path);
+        return;
+    }
+
+    let root = Path::new(&entry.from_root);
+    let mut dir = from.parent();
+    while let Some(d) = dir {
+        if d == root || !d.starts_with(root) || fs::remove_dir(d).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+}
+
+// `name_2.mp3` for `name.mp3`.
+fn numbered(path: &Path, n: u32) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}_{n}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{n}"),
+    };
+    path.with_file_name(name)
+}
+
+fn same_contents(a: &Path, b: &Path) -> io::Result<bool> {
+    if fs::metadata(a)?.len() != fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    let (mut a, mut b) = (File::open(a)?, File::open(b)?);
+    let mut buf_a = vec![0u8; COMPARE_CHUNK];
+    let mut buf_b = vec![0u8; COMPARE_CHUNK];
+    loop {
+        let n = read_chunk(&mut a, &mut buf_a)?;
+        if n == 0 {
+            return Ok(true);
+        }
+        if read_chunk(&mut b, &mut buf_b[..n])? != n || buf_a[..n] != buf_b[..n] {
+            return Ok(false);
+        }
+    }
+}
+
+// Fills `buf` unless the file ends first; returns the bytes read.
+fn read_chunk(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = file.read(&mut buf[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
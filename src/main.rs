@@ -4,35 +4,52 @@ mod adapters;
 mod application;
 mod audio_cache;
 mod audio_player;
+mod bandwidth;
 mod chapters;
 mod design;
 mod commands;
 mod components;
 mod db;
 mod download_manager;
+mod download_naming;
+mod download_reconcile;
+mod download_tags;
+mod download_scheduler;
 mod errors;
 mod events;
+mod feed;
+mod http_stream;
 mod hotkeys;
 mod image_cache;
+mod library_move;
 mod migrations;
+mod network_audit;
 mod notifier;
 mod orchestrator;
 mod pages;
 mod ports;
+mod retention;
 mod state;
+mod sync_pool;
+mod sync_scheduler;
+mod time_stretch;
+mod tracking_prefixes;
 mod tray;
 mod trim_silence;
 mod types;
 mod utils;
 
 use adapters::rfd_file_picker::RfdFilePicker;
+use adapters::reqwest_http_fetcher::ReqwestHttpFetcher;
 use adapters::rfd_folder_picker::RfdFolderPicker;
-use application::RCast;
+use application::{RCast, UiPorts};
 use audio_player::AudioPlayer;
 use db::Database;
 use download_manager::DownloadManager;
 use hotkeys::HotkeyManager;
+use network_audit::AuditedHttpFetcher;
 use orchestrator::Orchestrator;
+use ports::HttpFetcher;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc;
@@ -88,10 +105,22 @@ fn main() -> eframe::Result {
 
     let db = Database::new("rcast").expect("Failed to open database");
     let audio_player = AudioPlayer::new();
-    let download_manager = DownloadManager::new(db.clone());
+    let http: Arc<dyn HttpFetcher> = Arc::new(AuditedHttpFetcher::new(
+        Arc::new(ReqwestHttpFetcher::new()),
+        db.clone(),
+    ));
+    let download_manager = DownloadManager::new(db.clone(), http.clone());
 
     tokio_runtime.spawn(
-        Orchestrator::new(cmd_rx, event_tx, db, audio_player.clone(), download_manager).run(),
+        Orchestrator::new(
+            cmd_rx,
+            event_tx,
+            db,
+            audio_player.clone(),
+            download_manager,
+            http.clone(),
+        )
+        .run(),
     );
 
     let folder_picker: Arc<RfdFolderPicker> = Arc::new(RfdFolderPicker::new(tokio_runtime.clone()));
@@ -116,8 +145,11 @@ fn main() -> eframe::Result {
                 cmd_tx,
                 event_rx,
                 audio_player,
-                folder_picker,
-                file_picker,
+                UiPorts {
+                    folder_picker,
+                    file_picker,
+                    http,
+                },
                 tray,
                 hotkeys,
             )))
@@ -26,6 +26,16 @@ pub fn run_migrations(conn: &mut Connection) -> Result<()> {
         &versions::add_podcast_preferences::AddPodcastPreferences,
         &versions::add_episode_chapters_url::AddEpisodeChaptersUrl,
         &versions::add_episode_listen_time::AddEpisodeTotalListenTime,
+        &versions::add_podcast_http_validators::AddPodcastHttpValidators,
+        &versions::add_episode_guid::AddEpisodeGuid,
+        &versions::add_podcast_sync_schedule::AddPodcastSyncSchedule,
+        &versions::add_podcast_sync_health::AddPodcastSyncHealth,
+        &versions::create_network_audit_table::CreateNetworkAuditTable,
+        &versions::create_download_queue_table::CreateDownloadQueueTable,
+        &versions::add_episode_season_number::AddEpisodeSeasonNumber,
+        &versions::add_retention_rules::AddRetentionRules,
+        &versions::create_library_move_table::CreateLibraryMoveTable,
+        &versions::add_episode_downloaded_at::AddEpisodeDownloadedAt,
     ];
 
     let mut names = HashSet::new();
@@ -65,6 +75,16 @@ pub fn rollback_to(conn: &mut Connection, target_name: &str) -> Result<()> {
         &versions::add_podcast_preferences::AddPodcastPreferences,
         &versions::add_episode_chapters_url::AddEpisodeChaptersUrl,
         &versions::add_episode_listen_time::AddEpisodeTotalListenTime,
+        &versions::add_podcast_http_validators::AddPodcastHttpValidators,
+        &versions::add_episode_guid::AddEpisodeGuid,
+        &versions::add_podcast_sync_schedule::AddPodcastSyncSchedule,
+        &versions::add_podcast_sync_health::AddPodcastSyncHealth,
+        &versions::create_network_audit_table::CreateNetworkAuditTable,
+        &versions::create_download_queue_table::CreateDownloadQueueTable,
+        &versions::add_episode_season_number::AddEpisodeSeasonNumber,
+        &versions::add_retention_rules::AddRetentionRules,
+        &versions::create_library_move_table::CreateLibraryMoveTable,
+        &versions::add_episode_downloaded_at::AddEpisodeDownloadedAt,
     ];
 
     let target_pos = migrations
@@ -108,6 +128,16 @@ pub fn rollback_n(conn: &mut Connection, count: usize) -> Result<()> {
         &versions::add_podcast_preferences::AddPodcastPreferences,
         &versions::add_episode_chapters_url::AddEpisodeChaptersUrl,
         &versions::add_episode_listen_time::AddEpisodeTotalListenTime,
+        &versions::add_podcast_http_validators::AddPodcastHttpValidators,
+        &versions::add_episode_guid::AddEpisodeGuid,
+        &versions::add_podcast_sync_schedule::AddPodcastSyncSchedule,
+        &versions::add_podcast_sync_health::AddPodcastSyncHealth,
+        &versions::create_network_audit_table::CreateNetworkAuditTable,
+        &versions::create_download_queue_table::CreateDownloadQueueTable,
+        &versions::add_episode_season_number::AddEpisodeSeasonNumber,
+        &versions::add_retention_rules::AddRetentionRules,
+        &versions::create_library_move_table::CreateLibraryMoveTable,
+        &versions::add_episode_downloaded_at::AddEpisodeDownloadedAt,
     ];
 
     let limit: i64 = count as i64;
@@ -0,0 +1,24 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct AddEpisodeDownloadedAt;
+
+impl Migration for AddEpisodeDownloadedAt {
+    fn name(&self) -> &'static str {
+        "add_episode_downloaded_at"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE episodes ADD COLUMN downloaded_at INTEGER;
+             UPDATE episodes SET downloaded_at = unixepoch() WHERE download_status = 'downloaded';",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch("ALTER TABLE episodes DROP COLUMN downloaded_at;")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct AddEpisodeGuid;
+
+impl Migration for AddEpisodeGuid {
+    fn name(&self) -> &'static str {
+        "add_episode_guid"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        // Existing rows were keyed by enclosure URL, so seed their GUID with it. The next
+        // sync replaces it with the feed's real GUID (matched via the URL fallback).
+        // Two episodes may share an enclosure URL now, so the URL index stays for lookups
+        // but no longer enforces uniqueness.
+        transaction.execute_batch(
+            "ALTER TABLE episodes ADD COLUMN guid TEXT;
+             UPDATE episodes SET guid = url;
+             CREATE UNIQUE INDEX IF NOT EXISTS idx_episodes_podcast_guid
+                 ON episodes (podcast_id, guid) WHERE guid IS NOT NULL;
+             DROP INDEX IF EXISTS idx_episodes_podcast_url;
+             CREATE INDEX idx_episodes_podcast_url ON episodes (podcast_id, url);",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        // Episodes that share an enclosure URL are separate to the user, each with its own
+        // progress, bookmarks and download, so refuse rather than drop one of them.
+        let shared_urls: i64 = transaction.query_row(
+            "SELECT COUNT(*) FROM (
+                 SELECT 1 FROM episodes GROUP BY podcast_id, url HAVING COUNT(*) > 1
+             )",
+            [],
+            |row| row.get(0),
+        )?;
+        if shared_urls > 0 {
+            return Err(DatabaseError::migration_error(format!(
+                "cannot roll back {}: {shared_urls} enclosure URLs are shared by several episodes",
+                self.name()
+            )));
+        }
+
+        transaction.execute_batch(
+            "DROP INDEX IF EXISTS idx_episodes_podcast_url;
+             CREATE UNIQUE INDEX idx_episodes_podcast_url ON episodes (podcast_id, url);
+             DROP INDEX IF EXISTS idx_episodes_podcast_guid;
+             ALTER TABLE episodes DROP COLUMN guid;",
+        )?;
+        Ok(())
+    }
+}
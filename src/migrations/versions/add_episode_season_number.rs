@@ -0,0 +1,27 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct AddEpisodeSeasonNumber;
+
+impl Migration for AddEpisodeSeasonNumber {
+    fn name(&self) -> &'static str {
+        "add_episode_season_number"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE episodes ADD COLUMN season INTEGER;
+             ALTER TABLE episodes ADD COLUMN episode_number INTEGER;",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE episodes DROP COLUMN episode_number;
+             ALTER TABLE episodes DROP COLUMN season;",
+        )?;
+        Ok(())
+    }
+}
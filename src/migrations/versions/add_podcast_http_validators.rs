@@ -0,0 +1,27 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct AddPodcastHttpValidators;
+
+impl Migration for AddPodcastHttpValidators {
+    fn name(&self) -> &'static str {
+        "add_podcast_http_validators"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE podcasts ADD COLUMN etag TEXT;
+             ALTER TABLE podcasts ADD COLUMN last_modified TEXT;",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE podcasts DROP COLUMN etag;
+             ALTER TABLE podcasts DROP COLUMN last_modified;",
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct AddPodcastSyncHealth;
+
+impl Migration for AddPodcastSyncHealth {
+    fn name(&self) -> &'static str {
+        "add_podcast_sync_health"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE podcasts ADD COLUMN last_sync_attempt_at INTEGER;
+             ALTER TABLE podcasts ADD COLUMN sync_failure_count INTEGER NOT NULL DEFAULT 0;
+             ALTER TABLE podcasts ADD COLUMN sync_failing_since INTEGER;
+             ALTER TABLE podcasts ADD COLUMN sync_last_error TEXT;",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE podcasts DROP COLUMN last_sync_attempt_at;
+             ALTER TABLE podcasts DROP COLUMN sync_failure_count;
+             ALTER TABLE podcasts DROP COLUMN sync_failing_since;
+             ALTER TABLE podcasts DROP COLUMN sync_last_error;",
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct AddPodcastSyncSchedule;
+
+impl Migration for AddPodcastSyncSchedule {
+    fn name(&self) -> &'static str {
+        "add_podcast_sync_schedule"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE podcasts ADD COLUMN sync_interval_minutes INTEGER;
+             ALTER TABLE podcasts ADD COLUMN feed_ttl_minutes INTEGER;
+             ALTER TABLE podcasts ADD COLUMN feed_update_minutes INTEGER;
+             ALTER TABLE podcasts ADD COLUMN publish_interval_minutes INTEGER;",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE podcasts DROP COLUMN sync_interval_minutes;
+             ALTER TABLE podcasts DROP COLUMN feed_ttl_minutes;
+             ALTER TABLE podcasts DROP COLUMN feed_update_minutes;
+             ALTER TABLE podcasts DROP COLUMN publish_interval_minutes;",
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct AddRetentionRules;
+
+impl Migration for AddRetentionRules {
+    fn name(&self) -> &'static str {
+        "add_retention_rules"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE podcasts ADD COLUMN delete_played_after_days INTEGER;
+             ALTER TABLE podcasts ADD COLUMN delete_older_than_days INTEGER;
+             ALTER TABLE podcasts ADD COLUMN keep_unplayed INTEGER;
+             ALTER TABLE episodes ADD COLUMN is_protected INTEGER NOT NULL DEFAULT 0;
+             ALTER TABLE episodes ADD COLUMN played_at INTEGER;
+             UPDATE episodes SET played_at = updated_at WHERE is_played = 1;",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "ALTER TABLE episodes DROP COLUMN played_at;
+             ALTER TABLE episodes DROP COLUMN is_protected;
+             ALTER TABLE podcasts DROP COLUMN keep_unplayed;
+             ALTER TABLE podcasts DROP COLUMN delete_older_than_days;
+             ALTER TABLE podcasts DROP COLUMN delete_played_after_days;",
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct CreateDownloadQueueTable;
+
+impl Migration for CreateDownloadQueueTable {
+    fn name(&self) -> &'static str {
+        "create_download_queue_table"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "CREATE TABLE IF NOT EXISTS download_queue (
+                episode_id      INTEGER PRIMARY KEY REFERENCES episodes(id) ON DELETE CASCADE,
+                state           TEXT NOT NULL DEFAULT 'queued',  -- queued / paused
+                is_auto         INTEGER NOT NULL DEFAULT 0,      -- queued by auto-download
+                attempts        INTEGER NOT NULL DEFAULT 0,
+                next_attempt_at INTEGER NOT NULL DEFAULT 0,
+                last_error      TEXT,
+                created_at      INTEGER NOT NULL DEFAULT (unixepoch())
+            );",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch("DROP TABLE IF EXISTS download_queue;")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct CreateLibraryMoveTable;
+
+impl Migration for CreateLibraryMoveTable {
+    fn name(&self) -> &'static str {
+        "create_library_move_table"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "CREATE TABLE IF NOT EXISTS library_move (
+                episode_id INTEGER PRIMARY KEY REFERENCES episodes(id) ON DELETE CASCADE,
+                from_root  TEXT NOT NULL,
+                from_path  TEXT NOT NULL,
+                to_path    TEXT NOT NULL,
+                state      TEXT NOT NULL DEFAULT 'pending'  -- pending / copied / committed
+            );",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch("DROP TABLE IF EXISTS library_move;")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::errors::DatabaseError;
+use crate::migrations::versions::Migration;
+use rusqlite::Transaction;
+
+pub struct CreateNetworkAuditTable;
+
+impl Migration for CreateNetworkAuditTable {
+    fn name(&self) -> &'static str {
+        "create_network_audit_table"
+    }
+
+    fn up(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "CREATE TABLE IF NOT EXISTS network_audit (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp   INTEGER NOT NULL,
+                host        TEXT NOT NULL,
+                purpose     TEXT NOT NULL,    -- sync / download / image / chapters / stream
+                bytes       INTEGER NOT NULL DEFAULT 0,
+                result      TEXT NOT NULL     -- HTTP status, or the error for failed requests
+            );
+            CREATE INDEX IF NOT EXISTS idx_network_audit_purpose
+                ON network_audit (purpose);",
+        )?;
+        Ok(())
+    }
+
+    fn down(&self, transaction: &Transaction) -> Result<(), DatabaseError> {
+        transaction.execute_batch(
+            "DROP INDEX IF EXISTS idx_network_audit_purpose;
+             DROP TABLE IF EXISTS network_audit;",
+        )?;
+        Ok(())
+    }
+}
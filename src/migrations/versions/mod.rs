@@ -2,12 +2,22 @@ use crate::errors::DatabaseError;
 use rusqlite::Transaction;
 pub mod add_download_tracking;
 pub mod add_episode_chapters_url;
+pub mod add_episode_downloaded_at;
+pub mod add_episode_guid;
 pub mod add_episode_listen_time;
 pub mod add_episode_position;
+pub mod add_episode_season_number;
 pub mod add_episode_unique_index;
+pub mod add_podcast_http_validators;
 pub mod add_podcast_last_synced_at;
 pub mod add_podcast_preferences;
+pub mod add_podcast_sync_health;
+pub mod add_podcast_sync_schedule;
+pub mod add_retention_rules;
 pub mod create_bookmarks_table;
+pub mod create_download_queue_table;
+pub mod create_library_move_table;
+pub mod create_network_audit_table;
 pub mod initial_migration_02082026;
 pub trait Migration {
     fn name(&self) -> &'static str;
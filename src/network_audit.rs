@@ -0,0 +1,132 @@
+use std::io::Read;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::db::Database;
+use crate::ports::{HttpConfig, HttpError, HttpFetcher, HttpRequest, HttpResponse, RequestPurpose};
+
+/// Wraps the real fetcher and records every request in the `network_audit` table.
+///
+/// A request is logged once its response body is dropped, so the entry carries the number
+/// of bytes actually received. Requests that fail before a response arrives are logged
+/// straight away with the error as their result. Entries are written on a thread of their
+/// own, so finishing a request never waits on the database.
+pub struct AuditedHttpFetcher {
+    inner: Arc<dyn HttpFetcher>,
+    log: Sender<Record>,
+}
+
+impl AuditedHttpFetcher {
+    pub fn new(inner: Arc<dyn HttpFetcher>, db: Database) -> Self {
+        let (log, records) = mpsc::channel();
+        thread::spawn(move || write_records(db, records));
+        Self { inner, log }
+    }
+}
+
+impl HttpFetcher for AuditedHttpFetcher {
+    fn get(&self, request: HttpRequest) -> Result<HttpResponse, HttpError> {
+        let mut entry = PendingEntry {
+            log: self.log.clone(),
+            timestamp: chrono::Utc::now().timestamp(),
+            host: url::Url::parse(&request.url)
+                .ok()
+                .and_then(|u| u.host_str().map(str::to_string))
+                .unwrap_or_else(|| request.url.clone()),
+            purpose: request.purpose,
+            bytes: 0,
+            result: String::new(),
+        };
+
+        match self.inner.get(request) {
+            Ok(mut response) => {
+                entry.result = response.status.to_string();
+                response.body = Box::new(CountingBody {
+                    inner: response.body,
+                    entry,
+                    failed: false,
+                });
+                Ok(response)
+            }
+            Err(e) => {
+                entry.result = e.to_string();
+                Err(e)
+            }
+        }
+    }
+
+    fn configure(&self, config: HttpConfig) -> Result<(), HttpError> {
+        self.inner.configure(config)
+    }
+}
+
+// A finished request, on its way to the database.
+struct Record {
+    timestamp: i64,
+    host: String,
+    purpose: RequestPurpose,
+    bytes: i64,
+    result: String,
+}
+
+// Runs until every fetcher and response body that could send a record is gone.
+fn write_records(db: Database, records: Receiver<Record>) {
+    for record in records {
+        if let Err(e) = db.record_network_request_sync(
+            record.timestamp,
+            &record.host,
+            record.purpose,
+            record.bytes,
+            &record.result,
+        ) {
+            eprintln!("[audit] failed to record request to {}: {e}", record.host);
+        }
+    }
+}
+
+// Sent to the writer thread when dropped.
+struct PendingEntry {
+    log: Sender<Record>,
+    timestamp: i64,
+    host: String,
+    purpose: RequestPurpose,
+    bytes: i64,
+    result: String,
+}
+
+impl Drop for PendingEntry {
+    fn drop(&mut self) {
+        let _ = self.log.send(Record {
+            timestamp: self.timestamp,
+            host: std::mem::take(&mut self.host),
+            purpose: self.purpose,
+            bytes: self.bytes,
+            result: std::mem::take(&mut self.result),
+        });
+    }
+}
+
+struct CountingBody {
+    inner: Box<dyn Read + Send>,
+    entry: PendingEntry,
+    failed: bool,
+}
+
+impl Read for CountingBody {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.inner.read(buf) {
+            Ok(n) => {
+                self.entry.bytes += n as i64;
+                Ok(n)
+            }
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::Interrupted && !self.failed {
+                    self.failed = true;
+                    self.entry.result = format!("{} (interrupted: {e})", self.entry.result);
+                }
+                Err(e)
+            }
+        }
+    }
+}
@@ -14,7 +14,6 @@ pub fn notify_new_episodes(podcast_title: &str, count: usize) {
     });
 }
 
-#[allow(dead_code)]
 pub fn notify_download_complete(episode_title: &str) {
     let body = format!("Downloaded: {episode_title}");
     std::thread::spawn(move || {
@@ -1,22 +1,44 @@
+use std::sync::Arc;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
-use crate::audio_cache::AudioCache;
+use crate::audio_cache::{self, AudioCache, CachedAudio};
 use crate::audio_player::AudioPlayer;
 use crate::commands::AppCommand;
 use crate::components::toast::ToastMessage;
 use crate::db::Database;
-use crate::db::models::{DownloadStatus, Episode, Podcast};
+use crate::db::models::{DownloadStatus, DownloadedFile, Episode, Podcast};
 use crate::download_manager::DownloadManager;
+use crate::download_naming::DownloadName;
+use crate::download_reconcile::{Reconciliation, reconcile_downloads};
+use crate::download_scheduler::{self, DownloadScheduler};
 use crate::events::AppEvent;
-use crate::types::{Page, Settings};
+use crate::library_move::{MoveSummary, move_library, resume_library_move};
+use crate::feed::{ParsedFeed, parse_feed};
+use crate::http_stream::{HttpStream, total_len};
+use crate::ports::{HttpConfig, HttpError, HttpFetcher, HttpRequest, RequestPurpose};
+use crate::sync_pool::SyncPool;
+use crate::sync_scheduler;
+use crate::tracking_prefixes::{self, TrackingPrefixes};
+use crate::types::{Page, PodcastStorage, Settings};
+use crate::utils::string_utils::{format_bytes, format_time_of_day};
+
+// Entries shown at once on the settings page.
+const NETWORK_AUDIT_PAGE_SIZE: i64 = 500;
 
 pub struct Orchestrator {
     cmd_rx: UnboundedReceiver<AppCommand>,
     event_tx: UnboundedSender<AppEvent>,
     db: Database,
     audio_player: AudioPlayer,
-    audio_cache: AudioCache,
+    // Shared with prefetch tasks, which add the next queued episode when they finish.
+    audio_cache: Arc<std::sync::Mutex<AudioCache>>,
+    // Episode being prefetched, so the ticker starts each fetch only once.
+    prefetching: Arc<std::sync::Mutex<Option<i32>>>,
+    // Episodes too large for the prefetch limit or that failed to fetch, so the ticker doesn't
+    // try them again on every tick. Cleared when the next episode starts playing.
+    prefetch_skipped: Arc<std::sync::Mutex<std::collections::HashSet<i32>>>,
     download_manager: DownloadManager,
+    download_scheduler: DownloadScheduler,
     current_detail_podcast_id: Option<i32>,
     last_saved_position: f64,
     settings: Settings,
@@ -26,6 +48,10 @@ pub struct Orchestrator {
     // Listen-time tracking
     session_start: Option<std::time::Instant>,
     session_flushed_secs: u64,
+    sync_pool: SyncPool,
+    http: Arc<dyn HttpFetcher>,
+    // Held while the download library is being moved or checked, so the two never overlap.
+    library_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl Orchestrator {
@@ -35,14 +61,27 @@ impl Orchestrator {
         db: Database,
         audio_player: AudioPlayer,
         download_manager: DownloadManager,
+        http: Arc<dyn HttpFetcher>,
     ) -> Self {
+        let download_scheduler = DownloadScheduler::new(
+            db.clone(),
+            download_manager.clone(),
+            event_tx.clone(),
+            http.clone(),
+        );
         Self {
             cmd_rx,
             event_tx,
             db,
             audio_player,
-            audio_cache: AudioCache::new(2),
+            audio_cache: Arc::new(std::sync::Mutex::new({
+                let (memory, disk) = Settings::default().audio_cache_budgets();
+                AudioCache::new(memory, disk)
+            })),
+            prefetching: Arc::new(std::sync::Mutex::new(None)),
+            prefetch_skipped: Default::default(),
             download_manager,
+            download_scheduler,
             current_detail_podcast_id: None,
             last_saved_position: 0.0,
             settings: Settings::default(),
@@ -50,36 +89,45 @@ impl Orchestrator {
             sleep_timer_target: None,
             session_start: None,
             session_flushed_secs: 0,
+            sync_pool: SyncPool::new(Settings::default().sync_concurrency as usize),
+            http,
+            library_lock: Arc::new(tokio::sync::Mutex::new(())),
         }
     }
 
     pub async fn run(mut self) {
         let settings = self.db.get_settings().await.unwrap_or_default();
         self.audio_player.set_trim_silence_mode(settings.trim_silence_mode);
+        self.audio_player.set_preserve_pitch(settings.preserve_pitch);
         self.audio_player.set_speed(settings.default_speed);
+        self.sync_pool.set_concurrency(settings.sync_concurrency as usize);
+        self.apply_network_settings(&settings).await;
+        self.download_manager
+            .set_tracking_prefixes(TrackingPrefixes::from_settings(&settings));
+        self.download_manager
+            .set_bandwidth_limit(settings.download_rate_limit());
+        self.download_scheduler.set_settings(settings.clone());
+        self.apply_audio_cache_budgets(&settings);
         self.settings = settings.clone();
 
         let _ = self.event_tx.send(AppEvent::SettingsLoaded(settings.clone()));
         self.load_all_podcasts().await;
-
-        {
-            let tx = self.event_tx.clone();
-            let db = self.db.clone();
-            let interval_secs = (settings.sync_interval_minutes * 60) as u64;
-            tokio::spawn(async move {
-                let mut ticker =
-                    tokio::time::interval(std::time::Duration::from_secs(interval_secs));
-                ticker.tick().await;
-                loop {
-                    ticker.tick().await;
-                    background_sync(db.clone(), tx.clone()).await;
-                }
-            });
-        }
+        self.spawn_reconcile(false);
+        self.download_scheduler.restore().await;
 
         let mut save_ticker = tokio::time::interval(std::time::Duration::from_secs(10));
         save_ticker.tick().await;
 
+        // Per-podcast intervals are re-evaluated every minute against the current settings,
+        // so interval changes apply without a restart.
+        let mut sync_ticker = tokio::time::interval(std::time::Duration::from_secs(60));
+        sync_ticker.tick().await;
+
+        // Age-based retention rules expire downloads without anything else happening, so
+        // they are checked once at startup and then daily.
+        let mut retention_ticker =
+            tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+
         loop {
             tokio::select! {
                 Some(cmd) = self.cmd_rx.recv() => {
@@ -89,11 +137,122 @@ impl Orchestrator {
                     self.auto_save_position().await;
                     self.check_outro_skip().await;
                     self.check_sleep_timer().await;
+                    self.check_prefetch().await;
                 }
+                _ = sync_ticker.tick() => {
+                    self.run_scheduled_syncs().await;
+                    // Also picks up failed downloads whose retry delay has passed.
+                    self.download_scheduler.pump().await;
+                }
+                _ = retention_ticker.tick() => {
+                    self.apply_retention_to_all().await;
+                }
+                _ = self.download_scheduler.woken() => {
+                    self.download_scheduler.pump().await;
+                }
+            }
+        }
+    }
+
+    // Queues a background sync for every podcast whose interval (or backoff) has elapsed.
+    async fn run_scheduled_syncs(&self) {
+        let Ok(podcasts) = self.db.get_all_podcasts().await else {
+            return;
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        for podcast in podcasts {
+            if sync_scheduler::is_due(&podcast, &self.settings, now) {
+                self.queue_sync(&podcast);
             }
         }
     }
 
+    // Checks the download folder against the database in the background. The startup pass
+    // only speaks up when it finds something.
+    fn spawn_reconcile(&self, manual: bool) {
+        let db = self.db.clone();
+        let template = self.settings.download_name_template.clone();
+        let tx = self.event_tx.clone();
+        let lock = self.library_lock.clone();
+        tokio::spawn(async move {
+            let _guard = lock.lock().await;
+            // A move cut short by the last shutdown has to finish first, or the files it
+            // already moved would look missing.
+            finish_library_move(&db, &tx).await;
+
+            let Some(report) = reconcile(&db, &template, false, &tx).await else {
+                return;
+            };
+            let mut parts = Vec::new();
+            if report.folder_unavailable {
+                let held = plural(report.missing.len(), "download");
+                eprintln!("[downloads] download folder looks unavailable; left {held} as they are");
+                parts.push(if manual {
+                    format!("{held} missing; confirm in Settings to clear them")
+                } else {
+                    format!("Download folder unavailable, {held} left as they are")
+                });
+            } else if !report.missing.is_empty() {
+                parts.push(plural(report.missing.len(), "missing download"));
+            }
+            if !report.folder_unavailable && !report.found.is_empty() {
+                parts.push(format!("{} recovered", plural(report.found.len(), "download")));
+            }
+            if !report.unknown.is_empty() {
+                parts.push(format!(
+                    "{} in the download folder (see Settings)",
+                    plural(report.unknown.len(), "unknown file")
+                ));
+            }
+            if !parts.is_empty() {
+                let _ = tx.send(AppEvent::Toast(ToastMessage::info(&parts.join(", "))));
+            } else if manual {
+                let _ = tx.send(AppEvent::Toast(ToastMessage::success(
+                    "Downloads match the download folder",
+                )));
+            }
+        });
+    }
+
+    // Runs the retention rules of every podcast in the background.
+    async fn apply_retention_to_all(&self) {
+        let Ok(podcasts) = self.db.get_all_podcasts().await else {
+            return;
+        };
+        let scheduler = self.download_scheduler.clone();
+        tokio::spawn(async move {
+            for podcast in podcasts {
+                scheduler.apply_retention(podcast.id).await;
+            }
+        });
+    }
+
+    fn queue_sync(&self, podcast: &Podcast) {
+        let podcast_id = podcast.id;
+        let db = self.db.clone();
+        let tx = self.event_tx.clone();
+        let http = self.http.clone();
+        let download_scheduler = self.download_scheduler.clone();
+        let settings = self.settings.clone();
+        self.sync_pool.spawn(podcast_id, &podcast.url, async move {
+            sync_podcast(podcast_id, db, tx, http, download_scheduler, &settings).await;
+        });
+    }
+
+    // Rebuilds the HTTP client from the network settings. On failure the previous
+    // configuration stays active.
+    async fn apply_network_settings(&self, settings: &Settings) {
+        let http = self.http.clone();
+        let config = http_config(settings);
+        let result = tokio::task::spawn_blocking(move || http.configure(config)).await;
+        if let Ok(Err(e)) = result {
+            let _ = self
+                .event_tx
+                .send(AppEvent::Toast(ToastMessage::error(&e.to_string())));
+        }
+    }
+
     async fn auto_save_position(&mut self) {
         use crate::audio_player::PlaybackState;
 
@@ -150,6 +309,91 @@ impl Orchestrator {
         }
     }
 
+    fn apply_audio_cache_budgets(&self, settings: &Settings) {
+        let (memory, disk) = settings.audio_cache_budgets();
+        let cache = self.audio_cache.clone();
+        // Shrinking the memory budget may write spilled entries to disk.
+        tokio::task::spawn_blocking(move || {
+            let spills = cache.lock().unwrap().set_budgets(memory, disk);
+            audio_cache::write_spills(&cache, spills);
+        });
+    }
+
+    // Fetches the head of the queue into the audio cache once the current episode is within
+    // the prefetch horizon of its end, so the next episode starts without buffering.
+    async fn check_prefetch(&self) {
+        use crate::audio_player::PlaybackState;
+
+        let Some(limit) = self.settings.prefetch_limit_bytes() else {
+            return;
+        };
+        if self.audio_player.get_state() != PlaybackState::Playing {
+            return;
+        }
+        let duration = self.audio_player.get_duration().as_secs_f64();
+        let position = self.audio_player.get_position().as_secs_f64();
+        let horizon = self.settings.prefetch_minutes as f64 * 60.0;
+        if duration <= 0.0 || position < duration - horizon {
+            return;
+        }
+
+        let Ok(queue) = self.db.get_queue().await else {
+            return;
+        };
+        let Some(episode_id) = queue.first().map(|item| item.episode_id) else {
+            return;
+        };
+        if *self.prefetching.lock().unwrap() == Some(episode_id)
+            || self.prefetch_skipped.lock().unwrap().contains(&episode_id)
+            || self.audio_cache.lock().unwrap().contains(episode_id)
+        {
+            return;
+        }
+        let Ok(Some(episode)) = self.db.get_episode(episode_id).await else {
+            return;
+        };
+        // Downloaded episodes play from disk anyway.
+        if episode
+            .downloaded_path
+            .as_deref()
+            .is_some_and(|path| std::path::Path::new(path).exists())
+        {
+            return;
+        }
+
+        *self.prefetching.lock().unwrap() = Some(episode_id);
+        let http = self.http.clone();
+        let prefixes = TrackingPrefixes::from_settings(&self.settings);
+        let cache = self.audio_cache.clone();
+        let prefetching = self.prefetching.clone();
+        let skipped = self.prefetch_skipped.clone();
+        let tx = self.event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            match prefetch(http.as_ref(), &episode.url, prefixes.as_ref(), limit) {
+                Ok(Some(bytes)) => {
+                    let spills = cache.lock().unwrap().insert(episode_id, bytes);
+                    audio_cache::write_spills(&cache, spills);
+                    let _ = tx.send(AppEvent::AudioCacheLoaded(cache.lock().unwrap().stats()));
+                }
+                Ok(None) => {
+                    eprintln!(
+                        "[prefetch] '{}' is larger than the prefetch limit, skipping",
+                        episode.title
+                    );
+                    skipped.lock().unwrap().insert(episode_id);
+                }
+                Err(e) => {
+                    eprintln!("[prefetch] '{}' failed: {e}", episode.title);
+                    skipped.lock().unwrap().insert(episode_id);
+                }
+            }
+            let mut current = prefetching.lock().unwrap();
+            if *current == Some(episode_id) {
+                *current = None;
+            }
+        });
+    }
+
     async fn check_sleep_timer(&mut self) {
         let target = match self.sleep_timer_target {
             Some(t) => t,
@@ -222,13 +466,22 @@ impl Orchestrator {
             AppCommand::AddPodcast { feed_url } => {
                 let tx = self.event_tx.clone();
                 let db = self.db.clone();
+                let http = self.http.clone();
                 tokio::spawn(async move {
-                    add_podcast(feed_url, db, tx).await;
+                    add_podcast(feed_url, db, tx, http).await;
                 });
             }
             AppCommand::RemovePodcast(id) => {
+                // Cancelling clears the download path, so the downloads are listed first.
+                let downloaded = self.db.get_downloaded_episodes(id).await;
+                // Stop queued and running downloads so none of them writes a file back.
+                if let Ok(queued) = self.db.get_queued_download_ids(id).await {
+                    for episode_id in queued {
+                        self.download_scheduler.cancel(episode_id).await;
+                    }
+                }
                 // Delete downloaded episode files before removing the DB record.
-                if let Ok(downloaded) = self.db.get_downloaded_episodes(id).await {
+                if let Ok(downloaded) = downloaded {
                     for ep in downloaded {
                         if let Some(path) = ep.downloaded_path {
                             let _ = self.download_manager.delete_file(&path);
@@ -249,25 +502,47 @@ impl Orchestrator {
                     }
                 }
             }
+            AppCommand::MergePodcasts { from_id, into_id } => {
+                match self.db.merge_podcasts(from_id, into_id).await {
+                    Ok(orphaned_files) => {
+                        for path in orphaned_files {
+                            let _ = self.download_manager.delete_file(&path);
+                        }
+                        let _ = self.event_tx.send(AppEvent::PodcastRemoved(from_id));
+                        if let Ok(Some(podcast)) = self.db.get_podcast(into_id).await {
+                            let _ = self.event_tx.send(AppEvent::PodcastUpdated(podcast));
+                        }
+                        if let Ok(episodes) = self.db.get_episodes(into_id).await {
+                            let _ = self.event_tx.send(AppEvent::EpisodesUpdated {
+                                podcast_id: into_id,
+                                episodes,
+                            });
+                        }
+                        self.refresh_queue_display().await;
+                        let _ = self
+                            .event_tx
+                            .send(AppEvent::Toast(ToastMessage::success("Podcasts merged")));
+                    }
+                    Err(e) => {
+                        let _ = self
+                            .event_tx
+                            .send(AppEvent::Error(format!("Failed to merge podcasts: {e}")));
+                    }
+                }
+            }
             AppCommand::SyncPodcast(id) => {
-                let tx = self.event_tx.clone();
-                let db = self.db.clone();
-                let settings = self.settings.clone();
-                tokio::spawn(async move {
-                    sync_podcast(id, db, tx, &settings).await;
-                });
+                if let Ok(Some(podcast)) = self.db.get_podcast(id).await {
+                    self.queue_sync(&podcast);
+                }
             }
             AppCommand::SyncAll => {
-                let tx = self.event_tx.clone();
-                let db = self.db.clone();
-                let settings = self.settings.clone();
-                tokio::spawn(async move {
-                    background_sync_with_settings(db, tx, settings).await;
-                });
+                if let Ok(podcasts) = self.db.get_all_podcasts().await {
+                    for podcast in &podcasts {
+                        self.queue_sync(podcast);
+                    }
+                }
             }
             AppCommand::UpdatePodcastPreferences { podcast_id, prefs } => {
-                let should_enforce = prefs.keep_episodes_count.is_some()
-                    || self.settings.global_keep_episodes_count > 0;
                 match self
                     .db
                     .update_podcast_preferences(podcast_id, prefs.clone())
@@ -278,14 +553,10 @@ impl Orchestrator {
                             podcast_id,
                             prefs,
                         });
-                        if should_enforce {
-                            let db = self.db.clone();
-                            let dm = self.download_manager.clone();
-                            let settings = self.settings.clone();
-                            tokio::spawn(async move {
-                                enforce_retention_policy(podcast_id, &db, &dm, &settings).await;
-                            });
-                        }
+                        let scheduler = self.download_scheduler.clone();
+                        tokio::spawn(async move {
+                            scheduler.apply_retention(podcast_id).await;
+                        });
                     }
                     Err(e) => {
                         let _ = self.event_tx.send(AppEvent::Error(format!(
@@ -297,118 +568,26 @@ impl Orchestrator {
 
             // Episodes
             AppCommand::DownloadEpisode(episode_id) => {
-                let episode = match self.db.get_episode(episode_id).await {
-                    Ok(Some(e)) => e,
-                    _ => {
-                        let _ = self
-                            .event_tx
-                            .send(AppEvent::Error("Episode not found".into()));
-                        return;
-                    }
-                };
-
-                let podcast = match self.db.get_podcast(episode.podcast_id).await {
-                    Ok(Some(p)) => p,
-                    _ => {
-                        let _ = self
-                            .event_tx
-                            .send(AppEvent::Error("Podcast not found".into()));
-                        return;
-                    }
-                };
-
-                // Signal downloading state immediately.
-                let _ = self.event_tx.send(AppEvent::DownloadStatusChanged {
-                    episode_id,
-                    status: DownloadStatus::Downloading,
-                    path: None,
-                });
-                let _ = self
-                    .db
-                    .update_episode_download_status(episode_id, DownloadStatus::Downloading, None)
-                    .await;
-
-                let download_manager = self.download_manager.clone();
-                let db = self.db.clone();
-                let tx = self.event_tx.clone();
-                let settings = self.settings.clone();
-                let podcast_id = podcast.id;
+                self.download_scheduler.enqueue(episode_id, false).await;
+            }
 
-                tokio::task::spawn_blocking(move || {
-                    let folders = vec![podcast.title.clone()];
-                    let file_name = episode.title.clone();
-                    let ep_title = episode.title.clone();
+            AppCommand::PauseDownload(episode_id) => {
+                self.download_scheduler.pause(episode_id).await;
+            }
 
-                    // Skip if already downloaded.
-                    if let Some(existing) = download_manager.find_file(folders.clone(), &file_name)
-                    {
-                        let path_str = existing.to_string_lossy().to_string();
-                        let rt = tokio::runtime::Handle::current();
-                        rt.block_on(async {
-                            let _ = db
-                                .update_episode_download_status(
-                                    episode_id,
-                                    DownloadStatus::Downloaded,
-                                    Some(path_str.clone()),
-                                )
-                                .await;
-                        });
-                        let _ = tx.send(AppEvent::DownloadStatusChanged {
-                            episode_id,
-                            status: DownloadStatus::Downloaded,
-                            path: Some(path_str),
-                        });
-                        return;
-                    }
+            AppCommand::CancelDownload(episode_id) => {
+                self.download_scheduler.cancel(episode_id).await;
+            }
 
-                    match download_manager.download(episode.url, folders, file_name) {
-                        Ok(path) => {
-                            let path_str = path.to_string_lossy().to_string();
-                            let rt = tokio::runtime::Handle::current();
-                            rt.block_on(async {
-                                let _ = db
-                                    .update_episode_download_status(
-                                        episode_id,
-                                        DownloadStatus::Downloaded,
-                                        Some(path_str.clone()),
-                                    )
-                                    .await;
-                                enforce_retention_policy(podcast_id, &db, &download_manager, &settings).await;
-                            });
-                            if settings.notify_download_complete {
-                                send_notification("Download complete", &ep_title);
-                            }
-                            let _ = tx.send(AppEvent::DownloadStatusChanged {
-                                episode_id,
-                                status: DownloadStatus::Downloaded,
-                                path: Some(path_str),
-                            });
-                            let _ = tx.send(AppEvent::Toast(ToastMessage::success("Download complete")));
-                        }
-                        Err(e) => {
-                            let rt = tokio::runtime::Handle::current();
-                            rt.block_on(async {
-                                let _ = db
-                                    .update_episode_download_status(
-                                        episode_id,
-                                        DownloadStatus::Failed,
-                                        None,
-                                    )
-                                    .await;
-                            });
-                            let _ = tx.send(AppEvent::DownloadStatusChanged {
-                                episode_id,
-                                status: DownloadStatus::Failed,
-                                path: None,
-                            });
-                            let _ = tx.send(AppEvent::Error(format!("Download failed: {e}")));
-                        }
-                    }
-                });
+            AppCommand::RetryDownload(episode_id) => {
+                // Also resumes paused downloads from their partial file.
+                self.download_scheduler.enqueue(episode_id, false).await;
             }
 
             AppCommand::DeleteDownload(episode_id) => {
                 if let Ok(Some(ep)) = self.db.get_episode(episode_id).await {
+                    // A queued or running job would otherwise bring the file back.
+                    self.download_scheduler.cancel(episode_id).await;
                     if let Some(path) = ep.downloaded_path {
                         self.download_manager.delete_file(&path).ok();
                     }
@@ -420,10 +599,30 @@ impl Orchestrator {
                         episode_id,
                         status: DownloadStatus::NotDownloaded,
                         path: None,
+                        reason: None,
                     });
                 }
             }
 
+            AppCommand::RenameDownloads => {
+                let db = self.db.clone();
+                let dm = self.download_manager.clone();
+                let tx = self.event_tx.clone();
+                let template = self.settings.download_name_template.clone();
+                let detail_id = self.current_detail_podcast_id;
+                tokio::spawn(async move {
+                    rename_downloads(db.clone(), dm, &template, tx.clone()).await;
+                    if let Some(podcast_id) = detail_id
+                        && let Ok(episodes) = db.get_episodes(podcast_id).await
+                    {
+                        let _ = tx.send(AppEvent::EpisodesUpdated {
+                            podcast_id,
+                            episodes,
+                        });
+                    }
+                });
+            }
+
             AppCommand::TogglePlayed(episode_id) => {
                 if let Ok(Some(ep)) = self.db.get_episode(episode_id).await {
                     self.db
@@ -449,6 +648,26 @@ impl Orchestrator {
                 }
             }
 
+            AppCommand::SetEpisodeProtected {
+                episode_id,
+                protected,
+            } => {
+                if let Err(e) = self.db.update_episode_protected(episode_id, protected).await {
+                    let _ = self
+                        .event_tx
+                        .send(AppEvent::Error(format!("Failed to update episode: {e}")));
+                    return;
+                }
+                if let Some(podcast_id) = self.current_detail_podcast_id
+                    && let Ok(episodes) = self.db.get_episodes(podcast_id).await
+                {
+                    let _ = self.event_tx.send(AppEvent::EpisodesUpdated {
+                        podcast_id,
+                        episodes,
+                    });
+                }
+            }
+
             AppCommand::SetEpisodeSpeedPreset { episode_id, speed } => {
                 self.db
                     .update_episode_speed_preset(episode_id, speed)
@@ -667,8 +886,9 @@ impl Orchestrator {
             AppCommand::ImportOpml { path } => {
                 let tx = self.event_tx.clone();
                 let db = self.db.clone();
+                let http = self.http.clone();
                 tokio::spawn(async move {
-                    import_opml(path, db, tx).await;
+                    import_opml(path, db, tx, http).await;
                 });
             }
             AppCommand::ExportOpml { path } => {
@@ -681,11 +901,30 @@ impl Orchestrator {
 
             // Settings
             AppCommand::SaveSettings(settings) => {
+                let settings = *settings;
                 match self.db.save_settings(settings.clone()).await {
                     Ok(_) => {
                         self.audio_player.set_trim_silence_mode(settings.trim_silence_mode);
+                        self.audio_player.set_preserve_pitch(settings.preserve_pitch);
+                        self.sync_pool.set_concurrency(settings.sync_concurrency as usize);
+                        if http_config(&settings) != http_config(&self.settings) {
+                            self.apply_network_settings(&settings).await;
+                        }
+                        self.download_manager
+                            .set_tracking_prefixes(TrackingPrefixes::from_settings(&settings));
+                        self.download_manager
+                            .set_bandwidth_limit(settings.download_rate_limit());
+                        self.download_scheduler.set_settings(settings.clone());
+                        if settings.audio_cache_budgets() != self.settings.audio_cache_budgets() {
+                            self.apply_audio_cache_budgets(&settings);
+                        }
+                        let retention_changed =
+                            retention_config(&settings) != retention_config(&self.settings);
                         self.settings = settings;
                         let _ = self.event_tx.send(AppEvent::SettingsSaved);
+                        if retention_changed {
+                            self.apply_retention_to_all().await;
+                        }
                     }
                     Err(e) => {
                         let _ = self
@@ -728,6 +967,113 @@ impl Orchestrator {
                     }
                 }
             }
+
+            // Network audit
+            AppCommand::LoadNetworkAudit { purpose, host } => {
+                match self
+                    .db
+                    .get_network_audit(purpose, host, NETWORK_AUDIT_PAGE_SIZE)
+                    .await
+                {
+                    Ok(entries) => {
+                        let _ = self.event_tx.send(AppEvent::NetworkAuditLoaded(entries));
+                    }
+                    Err(e) => {
+                        let _ = self.event_tx.send(AppEvent::Error(format!(
+                            "Failed to load network activity: {e}"
+                        )));
+                    }
+                }
+            }
+            AppCommand::ClearNetworkAudit => match self.db.clear_network_audit().await {
+                Ok(()) => {
+                    let _ = self.event_tx.send(AppEvent::NetworkAuditLoaded(Vec::new()));
+                }
+                Err(e) => {
+                    let _ = self.event_tx.send(AppEvent::Error(format!(
+                        "Failed to clear network activity: {e}"
+                    )));
+                }
+            },
+
+            // Storage
+            AppCommand::LoadStorageUsage => {
+                let dm = self.download_manager.clone();
+                let tx = self.event_tx.clone();
+                let _ = tx.send(AppEvent::AudioCacheLoaded(
+                    self.audio_cache.lock().unwrap().stats(),
+                ));
+                tokio::task::spawn_blocking(move || {
+                    let _ = tx.send(AppEvent::StorageUsageLoaded(storage_usage(&dm)));
+                });
+            }
+            AppCommand::ClearAudioCache => {
+                let cache = self.audio_cache.clone();
+                let tx = self.event_tx.clone();
+                tokio::task::spawn_blocking(move || {
+                    let dir = cache.lock().unwrap().clear();
+                    audio_cache::remove_spill_dir(&dir);
+                    let _ = tx.send(AppEvent::AudioCacheLoaded(cache.lock().unwrap().stats()));
+                    let _ = tx.send(AppEvent::Toast(ToastMessage::success("Audio cache cleared")));
+                });
+            }
+            AppCommand::DeletePlayedDownloads(podcast_id) => {
+                let dm = self.download_manager.clone();
+                let tx = self.event_tx.clone();
+                tokio::task::spawn_blocking(move || {
+                    delete_downloads(&dm, &tx, |f| {
+                        f.is_played
+                            && !f.is_protected
+                            && podcast_id.is_none_or(|id| f.podcast_id == id)
+                    });
+                });
+            }
+            AppCommand::DeletePodcastDownloads(podcast_id) => {
+                let dm = self.download_manager.clone();
+                let tx = self.event_tx.clone();
+                tokio::task::spawn_blocking(move || {
+                    delete_downloads(&dm, &tx, |f| f.podcast_id == podcast_id);
+                });
+            }
+            AppCommand::MoveLibrary { from, to } => {
+                let db = self.db.clone();
+                let tx = self.event_tx.clone();
+                let lock = self.library_lock.clone();
+                tokio::spawn(async move {
+                    let _guard = lock.lock().await;
+                    let result = move_library(&db, &from, &to, &tx).await;
+                    report_library_move(result, &tx);
+                });
+            }
+            AppCommand::ReconcileDownloads => {
+                self.spawn_reconcile(true);
+            }
+            AppCommand::ClearMissingDownloads => {
+                let db = self.db.clone();
+                let template = self.settings.download_name_template.clone();
+                let tx = self.event_tx.clone();
+                let lock = self.library_lock.clone();
+                tokio::spawn(async move {
+                    let _guard = lock.lock().await;
+                    if let Some(report) = reconcile(&db, &template, true, &tx).await {
+                        let _ = tx.send(AppEvent::Toast(ToastMessage::info(&format!(
+                            "Marked {} as not downloaded",
+                            plural(report.missing.len(), "download")
+                        ))));
+                    }
+                });
+            }
+            AppCommand::DeleteUnknownFiles(paths) => {
+                let db = self.db.clone();
+                let dm = self.download_manager.clone();
+                let template = self.settings.download_name_template.clone();
+                let tx = self.event_tx.clone();
+                let lock = self.library_lock.clone();
+                tokio::spawn(async move {
+                    let _guard = lock.lock().await;
+                    delete_unknown_files(db, dm, template, paths, tx).await;
+                });
+            }
         }
     }
 
@@ -798,6 +1144,7 @@ impl Orchestrator {
             }
         };
 
+        self.prefetch_skipped.lock().unwrap().clear();
         let podcast_id = episode.podcast_id;
         let resume_position = episode.position_seconds;
         let episode_for_event = episode.clone();
@@ -807,8 +1154,9 @@ impl Orchestrator {
         // Fetch chapters concurrently if this episode has a chapters URL.
         if let Some(chapters_url) = episode.chapters_url.clone() {
             let chapter_tx = tx.clone();
+            let http = self.http.clone();
             tokio::spawn(async move {
-                if let Ok(chapters) = crate::chapters::fetch_chapters(&chapters_url).await {
+                if let Ok(chapters) = crate::chapters::fetch_chapters(http, &chapters_url).await {
                     let _ = chapter_tx.send(AppEvent::ChaptersLoaded(chapters));
                 }
             });
@@ -880,54 +1228,20 @@ impl Orchestrator {
             return;
         }
 
-        // Tier 2: legacy file scan (backwards compat).
-        let downloaded_path = self
-            .download_manager
-            .find_file(vec![podcast.title.clone()], &episode.title);
-
-        if let Some(path) = downloaded_path {
-            let path_str = path.to_string_lossy().to_string();
+        // A missing file may only mean the download folder is unmounted, so the download
+        // state is left for reconciliation to settle. Fall back to streaming.
+        // Tier 2: audio cache, in memory or spilled to disk.
+        let cached = self.audio_cache.lock().unwrap().get(episode_id);
+        if let Some(cached) = cached {
             let ep = episode_for_event.clone();
             tokio::task::spawn_blocking(move || {
-                match audio_player.play_from_file(&path_str, episode_id) {
-                    Ok(_) => {
-                        let seek_to = if intro_skip > 0 && !should_resume {
-                            std::time::Duration::from_secs(intro_skip as u64)
-                        } else if should_resume {
-                            std::time::Duration::from_secs_f64(resume_position)
-                        } else {
-                            std::time::Duration::ZERO
-                        };
-                        if seek_to > std::time::Duration::ZERO {
-                            audio_player.seek(seek_to);
-                            if should_resume {
-                                let mins = (resume_position as u64) / 60;
-                                let secs = (resume_position as u64) % 60;
-                                let _ = tx.send(AppEvent::Toast(ToastMessage::info(&format!(
-                                    "Resuming from {:02}:{:02}",
-                                    mins, secs
-                                ))));
-                            }
-                        }
-                        let _ = tx.send(AppEvent::PlaybackStarted {
-                            episode_id,
-                            podcast_id,
-                            episode: ep,
-                        });
-                    }
-                    Err(e) => {
-                        let _ = tx.send(AppEvent::Error(format!("Playback failed: {e}")));
+                let result = match cached {
+                    CachedAudio::Memory(bytes) => audio_player.play_from_memory(bytes, episode_id),
+                    CachedAudio::File(path) => {
+                        audio_player.play_from_file(&path.to_string_lossy(), episode_id)
                     }
-                }
-            });
-            return;
-        }
-
-        // Tier 3: in-memory audio cache.
-        if let Some(bytes_data) = self.audio_cache.get(episode_id) {
-            let ep = episode_for_event.clone();
-            tokio::task::spawn_blocking(move || {
-                match audio_player.play_from_memory(bytes_data, episode_id) {
+                };
+                match result {
                     Ok(_) => {
                         let seek_to = if intro_skip > 0 && !should_resume {
                             std::time::Duration::from_secs(intro_skip as u64)
@@ -961,63 +1275,52 @@ impl Orchestrator {
             return;
         }
 
-        // Tier 4: fetch from network.
+        // Tier 3: stream from the network, playing while the rest downloads.
         let url = episode.url.clone();
         let _ = tx.send(AppEvent::Toast(ToastMessage::info("Buffering...")));
 
-        let fetch_tx = tx.clone();
         let ep = episode_for_event.clone();
-        match tokio::task::spawn_blocking(move || {
-            reqwest::blocking::get(&url)
-                .and_then(|r| r.bytes())
-                .map_err(|e| e.to_string())
-        })
-        .await
-        {
-            Ok(Ok(raw)) => {
-                let bytes_data = raw;
-                self.audio_cache.insert(episode_id, bytes_data.clone());
-                let audio_player2 = audio_player.clone();
-                tokio::task::spawn_blocking(move || {
-                    match audio_player2.play_from_memory(bytes_data, episode_id) {
-                        Ok(_) => {
-                            let seek_to = if intro_skip > 0 && !should_resume {
-                                std::time::Duration::from_secs(intro_skip as u64)
-                            } else if should_resume {
-                                std::time::Duration::from_secs_f64(resume_position)
-                            } else {
-                                std::time::Duration::ZERO
-                            };
-                            if seek_to > std::time::Duration::ZERO {
-                                audio_player2.seek(seek_to);
-                                if should_resume {
-                                    let mins = (resume_position as u64) / 60;
-                                    let secs = (resume_position as u64) % 60;
-                                    let _ = fetch_tx.send(AppEvent::Toast(ToastMessage::info(
-                                        &format!("Resuming from {:02}:{:02}", mins, secs),
-                                    )));
-                                }
-                            }
-                            let _ = fetch_tx.send(AppEvent::PlaybackStarted {
-                                episode_id,
-                                podcast_id,
-                                episode: ep,
-                            });
-                        }
-                        Err(e) => {
-                            let _ =
-                                fetch_tx.send(AppEvent::Error(format!("Playback failed: {e}")));
+        let http = self.http.clone();
+        let prefixes = TrackingPrefixes::from_settings(&self.settings);
+        tokio::task::spawn_blocking(move || {
+            let stream = match HttpStream::open(http, url, prefixes) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = tx.send(AppEvent::Error(format!("Fetch failed: {e}")));
+                    return;
+                }
+            };
+            match audio_player.play_from_stream(stream, episode_id) {
+                Ok(_) => {
+                    let seek_to = if intro_skip > 0 && !should_resume {
+                        std::time::Duration::from_secs(intro_skip as u64)
+                    } else if should_resume {
+                        std::time::Duration::from_secs_f64(resume_position)
+                    } else {
+                        std::time::Duration::ZERO
+                    };
+                    if seek_to > std::time::Duration::ZERO {
+                        audio_player.seek(seek_to);
+                        if should_resume {
+                            let mins = (resume_position as u64) / 60;
+                            let secs = (resume_position as u64) % 60;
+                            let _ = tx.send(AppEvent::Toast(ToastMessage::info(&format!(
+                                "Resuming from {:02}:{:02}",
+                                mins, secs
+                            ))));
                         }
                     }
-                });
-            }
-            Ok(Err(e)) => {
-                let _ = fetch_tx.send(AppEvent::Error(format!("Fetch failed: {e}")));
-            }
-            Err(e) => {
-                let _ = fetch_tx.send(AppEvent::Error(format!("Task failed: {e}")));
+                    let _ = tx.send(AppEvent::PlaybackStarted {
+                        episode_id,
+                        podcast_id,
+                        episode: ep,
+                    });
+                }
+                Err(e) => {
+                    let _ = tx.send(AppEvent::Error(format!("Playback failed: {e}")));
+                }
             }
-        }
+        });
     }
 
     async fn refresh_queue_display(&self) {
@@ -1037,38 +1340,33 @@ impl Orchestrator {
 
 // -- Standalone async helpers ---------------------------------------------------
 
-async fn enforce_retention_policy(
-    podcast_id: i32,
-    db: &Database,
-    dm: &DownloadManager,
-    settings: &Settings,
-) {
-    let podcast = match db.get_podcast(podcast_id).await {
-        Ok(Some(p)) => p,
-        _ => return,
-    };
-
-    let effective_keep = podcast
-        .keep_episodes_count
-        .unwrap_or(settings.global_keep_episodes_count);
-
-    if effective_keep <= 0 {
-        return; // 0 = keep all
+// The whole episode, or None if it is larger than `limit` bytes. Only the first `limit` bytes
+// are asked for, so a server that supports ranges reports the full size up front and a file
+// that is too large is dropped without reading it.
+fn prefetch(
+    http: &dyn HttpFetcher,
+    url: &str,
+    prefixes: Option<&TrackingPrefixes>,
+    limit: u64,
+) -> Result<Option<bytes::Bytes>, HttpError> {
+    use std::io::Read;
+
+    let request = HttpRequest::get(RequestPurpose::Stream, url)
+        .header("Range", format!("bytes=0-{}", limit.saturating_sub(1)));
+    let response = tracking_prefixes::get_direct(http, request, prefixes)?;
+    let length = total_len(&response);
+    // Without the total, a partial response can't tell a file of exactly `limit` bytes from
+    // a larger one.
+    if length.is_some_and(|length| length > limit) || (response.status == 206 && length.is_none()) {
+        return Ok(None);
     }
 
-    let downloaded = match db.get_downloaded_episodes(podcast_id).await {
-        Ok(eps) => eps,
-        Err(_) => return,
-    };
-
-    for ep in downloaded.into_iter().skip(effective_keep as usize) {
-        if let Some(path) = &ep.downloaded_path {
-            dm.delete_file(path).ok();
-        }
-        db.update_episode_download_status(ep.id, DownloadStatus::NotDownloaded, None)
-            .await
-            .ok();
+    let mut buf = Vec::new();
+    response.body.take(limit + 1).read_to_end(&mut buf)?;
+    if buf.len() as u64 > limit {
+        return Ok(None);
     }
+    Ok(Some(bytes::Bytes::from(buf)))
 }
 
 fn send_notification(title: &str, body: &str) {
@@ -1083,20 +1381,246 @@ fn send_notification(title: &str, body: &str) {
     });
 }
 
+// Per-podcast totals, largest first.
+fn storage_usage(dm: &DownloadManager) -> Vec<PodcastStorage> {
+    let mut by_podcast: Vec<PodcastStorage> = Vec::new();
+    for (file, size) in dm.downloaded_files() {
+        let entry = match by_podcast.iter_mut().find(|p| p.podcast_id == file.podcast_id) {
+            Some(entry) => entry,
+            None => {
+                by_podcast.push(PodcastStorage {
+                    podcast_id: file.podcast_id,
+                    podcast_title: file.podcast_title.clone(),
+                    files: 0,
+                    bytes: 0,
+                    played_bytes: 0,
+                });
+                by_podcast.last_mut().unwrap()
+            }
+        };
+        entry.files += 1;
+        entry.bytes += size;
+        if file.is_played && !file.is_protected {
+            entry.played_bytes += size;
+        }
+    }
+    by_podcast.sort_by_key(|p| std::cmp::Reverse(p.bytes));
+    by_podcast
+}
+
+// Blocking: deletes every download matching `filter`, then reports the new usage.
+fn delete_downloads(
+    dm: &DownloadManager,
+    tx: &UnboundedSender<AppEvent>,
+    filter: impl Fn(&DownloadedFile) -> bool,
+) {
+    let mut count = 0;
+    let mut freed = 0;
+    for (file, size) in dm.downloaded_files() {
+        if filter(&file) && dm.remove_download(&file).is_ok() {
+            count += 1;
+            freed += size;
+            let _ = tx.send(AppEvent::DownloadStatusChanged {
+                episode_id: file.episode_id,
+                status: DownloadStatus::NotDownloaded,
+                path: None,
+                reason: None,
+            });
+        }
+    }
+
+    let _ = tx.send(AppEvent::Toast(ToastMessage::success(&format!(
+        "Deleted {count} download{} ({})",
+        if count == 1 { "" } else { "s" },
+        format_bytes(freed as i64)
+    ))));
+    let _ = tx.send(AppEvent::StorageUsageLoaded(storage_usage(dm)));
+}
+
 // -- Standalone async task functions --------------------------------------------
 
-async fn add_podcast(feed_url: String, db: Database, tx: UnboundedSender<AppEvent>) {
+async fn rename_downloads(
+    db: Database,
+    dm: DownloadManager,
+    template: &str,
+    tx: UnboundedSender<AppEvent>,
+) {
+    let podcasts = db.get_all_podcasts().await.unwrap_or_default();
+    let mut moved = 0;
+    let mut failed = 0;
+
+    for podcast in podcasts {
+        let episodes = db.get_downloaded_episodes(podcast.id).await.unwrap_or_default();
+        for episode in episodes {
+            let Some(current) = episode.downloaded_path.clone() else {
+                continue;
+            };
+            let name = DownloadName::new(template, &podcast, &episode);
+            let dm = dm.clone();
+            let episode_id = episode.id;
+            let old = current.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                dm.relocate(std::path::Path::new(&old), &name, episode_id)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(path)) => {
+                    let path = path.to_string_lossy().to_string();
+                    if path != current {
+                        let _ = db
+                            .update_episode_download_status(
+                                episode_id,
+                                DownloadStatus::Downloaded,
+                                Some(path),
+                            )
+                            .await;
+                        moved += 1;
+                    }
+                }
+                Ok(Err(e)) => {
+                    eprintln!("[downloads] could not move '{current}': {e}");
+                    failed += 1;
+                }
+                Err(_) => failed += 1,
+            }
+        }
+    }
+
+    let message = format!(
+        "Renamed {moved} download{}",
+        if moved == 1 { "" } else { "s" }
+    );
+    let toast = if failed > 0 {
+        ToastMessage::error(&format!("{message}, {failed} could not be moved"))
+    } else {
+        ToastMessage::success(&message)
+    };
+    let _ = tx.send(AppEvent::Toast(toast));
+}
+
+async fn finish_library_move(db: &Database, tx: &UnboundedSender<AppEvent>) {
+    match db.get_library_move().await {
+        Ok(entries) if !entries.is_empty() => {
+            let _ = tx.send(AppEvent::Toast(ToastMessage::info(
+                "Finishing the move of your downloads...",
+            )));
+            report_library_move(resume_library_move(db, tx).await, tx);
+        }
+        _ => {}
+    }
+}
+
+fn report_library_move(result: anyhow::Result<MoveSummary>, tx: &UnboundedSender<AppEvent>) {
+    let _ = tx.send(AppEvent::LibraryMoveFinished);
+    let toast = match result {
+        Ok(summary) if summary.failed > 0 => ToastMessage::error(&format!(
+            "Moved {}, {} could not be moved",
+            plural(summary.moved, "download"),
+            summary.failed
+        )),
+        Ok(summary) => ToastMessage::success(&format!("Moved {}", plural(summary.moved, "download"))),
+        Err(e) => ToastMessage::error(&format!("Could not move downloads: {e}")),
+    };
+    let _ = tx.send(AppEvent::Toast(toast));
+}
+
+// Runs a reconciliation pass and sends the corrected download states to the UI. When the
+// pass was held back, the missing downloads are sent for the user to confirm instead.
+async fn reconcile(
+    db: &Database,
+    template: &str,
+    clear_missing: bool,
+    tx: &UnboundedSender<AppEvent>,
+) -> Option<Reconciliation> {
+    let report = match reconcile_downloads(db, template, clear_missing).await {
+        Ok(report) => report,
+        Err(e) => {
+            let _ = tx.send(AppEvent::Error(format!("Could not check downloads: {e}")));
+            return None;
+        }
+    };
+
+    if report.folder_unavailable && !clear_missing {
+        let _ = tx.send(AppEvent::MissingDownloadsHeld(report.missing.clone()));
+        let _ = tx.send(AppEvent::DownloadsReconciled(report.unknown.clone()));
+        return Some(report);
+    }
+    let _ = tx.send(AppEvent::MissingDownloadsHeld(Vec::new()));
+    for &episode_id in &report.missing {
+        let _ = tx.send(AppEvent::DownloadStatusChanged {
+            episode_id,
+            status: DownloadStatus::NotDownloaded,
+            path: None,
+            reason: None,
+        });
+    }
+    for (episode_id, path) in &report.found {
+        let _ = tx.send(AppEvent::DownloadStatusChanged {
+            episode_id: *episode_id,
+            status: DownloadStatus::Downloaded,
+            path: Some(path.clone()),
+            reason: None,
+        });
+    }
+    let _ = tx.send(AppEvent::DownloadsReconciled(report.unknown.clone()));
+    Some(report)
+}
+
+// Deletes `paths`, skipping any that a fresh reconciliation pass no longer reports as unknown.
+async fn delete_unknown_files(
+    db: Database,
+    dm: DownloadManager,
+    template: String,
+    paths: Vec<std::path::PathBuf>,
+    tx: UnboundedSender<AppEvent>,
+) {
+    let Some(report) = reconcile(&db, &template, false, &tx).await else {
+        return;
+    };
+
+    let mut count = 0;
+    let mut freed = 0;
+    let mut remaining = Vec::new();
+    for file in report.unknown {
+        if paths.contains(&file.path) && dm.delete_file(&file.path.to_string_lossy()).is_ok() {
+            count += 1;
+            freed += file.bytes;
+        } else {
+            remaining.push(file);
+        }
+    }
+
+    let _ = tx.send(AppEvent::DownloadsReconciled(remaining));
+    let _ = tx.send(AppEvent::Toast(ToastMessage::success(&format!(
+        "Deleted {} ({})",
+        plural(count, "file"),
+        format_bytes(freed as i64)
+    ))));
+}
+
+// "1 file", "3 files".
+fn plural(count: usize, noun: &str) -> String {
+    format!("{count} {noun}{}", if count == 1 { "" } else { "s" })
+}
+
+async fn add_podcast(
+    feed_url: String,
+    db: Database,
+    tx: UnboundedSender<AppEvent>,
+    http: Arc<dyn HttpFetcher>,
+) {
     let _ = tx.send(AppEvent::Toast(ToastMessage::info("Fetching feed...")));
 
-    match fetch_feed(&feed_url).await {
-        Ok((title, description, image_url, episodes)) => {
+    match fetch_new_feed(&http, &feed_url).await {
+        Ok((feed, validators, feed_url)) => {
             let now = chrono::Utc::now().timestamp();
             let podcast = Podcast {
                 id: 0,
                 url: feed_url,
-                title,
-                description,
-                image_url,
+                title: feed.title,
+                description: feed.description,
+                image_url: feed.image_url,
                 episode_count: 0,
                 last_synced_at: now,
                 created_at: now,
@@ -1104,13 +1628,27 @@ async fn add_podcast(feed_url: String, db: Database, tx: UnboundedSender<AppEven
                 speed_preset: None,
                 auto_download: None,
                 keep_episodes_count: None,
+                delete_played_after_days: None,
+                delete_older_than_days: None,
+                keep_unplayed: None,
                 skip_intro_seconds: 0,
                 skip_outro_seconds: 0,
+                etag: validators.etag,
+                last_modified: validators.last_modified,
+                sync_interval_minutes: None,
+                feed_ttl_minutes: feed.ttl_minutes,
+                feed_update_minutes: feed.update_minutes,
+                publish_interval_minutes: sync_scheduler::publish_interval_minutes(&feed.episodes, now),
+                last_sync_attempt_at: Some(now),
+                sync_failure_count: 0,
+                sync_failing_since: None,
+                sync_last_error: None,
             };
 
             match db.insert_podcast(podcast).await {
                 Ok(saved) => {
-                    let episodes_with_id: Vec<Episode> = episodes
+                    let episodes_with_id: Vec<Episode> = feed
+                        .episodes
                         .into_iter()
                         .map(|mut e| {
                             e.podcast_id = saved.id;
@@ -1137,6 +1675,8 @@ async fn sync_podcast(
     podcast_id: i32,
     db: Database,
     tx: UnboundedSender<AppEvent>,
+    http: Arc<dyn HttpFetcher>,
+    download_scheduler: DownloadScheduler,
     settings: &Settings,
 ) {
     let podcast = match db.get_podcast(podcast_id).await {
@@ -1145,9 +1685,37 @@ async fn sync_podcast(
     };
 
     let _ = tx.send(AppEvent::SyncStarted(podcast_id));
+    let _ = db.record_sync_attempt(podcast_id).await;
+
+    let validators = FeedValidators {
+        etag: podcast.etag.clone(),
+        last_modified: podcast.last_modified.clone(),
+    };
+
+    match fetch_feed(&http, &podcast.url, &validators).await {
+        Ok((FeedResponse::NotModified, moved_to)) => {
+            // Nothing changed upstream — skip parsing and episode writes entirely.
+            let _ = db.update_podcast_synced_at(podcast_id).await;
+            if let Some(new_url) = moved_to {
+                follow_feed_move(&podcast, new_url, &db, &tx).await;
+            }
+        }
+        Ok((FeedResponse::Modified(feed, validators), moved_to)) => {
+            let ParsedFeed {
+                title,
+                description,
+                image_url,
+                episodes,
+                new_feed_url,
+                ttl_minutes,
+                update_minutes,
+            } = feed;
+
+            // Pick up rebrands and new artwork.
+            let _ = db
+                .update_podcast_metadata(podcast_id, title, description, image_url)
+                .await;
 
-    match fetch_feed(&podcast.url).await {
-        Ok((_, _, _, episodes)) => {
             let now = chrono::Utc::now().timestamp();
             let episodes_with_id: Vec<Episode> = episodes
                 .into_iter()
@@ -1159,7 +1727,15 @@ async fn sync_podcast(
                 })
                 .collect();
 
-            let new_ids = db.insert_episodes(episodes_with_id).await.unwrap_or_default();
+            let inserted = db.insert_episodes(episodes_with_id).await;
+            // Only remember the validators once the episodes are stored, otherwise a failed
+            // write would be hidden behind 304s on every following sync.
+            if inserted.is_ok() {
+                let _ = db
+                    .update_podcast_validators(podcast_id, validators.etag, validators.last_modified)
+                    .await;
+            }
+            let new_ids = inserted.unwrap_or_default();
 
             if !new_ids.is_empty() && settings.notify_new_episodes {
                 let count = new_ids.len();
@@ -1173,57 +1749,35 @@ async fn sync_podcast(
             // Auto-download new episodes if configured.
             let effective_auto = podcast.auto_download.unwrap_or(settings.auto_download_new_episodes);
             if effective_auto && !new_ids.is_empty() {
+                let message = if settings
+                    .allows_auto_download_at(download_scheduler::local_minute_of_day())
+                {
+                    "Auto-downloading new episodes...".to_string()
+                } else {
+                    format!(
+                        "New episodes will download at {}",
+                        format_time_of_day(settings.auto_download_window_start)
+                    )
+                };
+                let _ = tx.send(AppEvent::Toast(ToastMessage::info(&message)));
                 for episode_id in new_ids {
-                    let _ = tx.send(AppEvent::Toast(ToastMessage::info("Auto-downloading new episode...")));
-                    // We fire off a DownloadEpisode-equivalent inline rather than going through the
-                    // command channel (which we don't have access to here).  The download path is
-                    // the same — fetch the episode and call the download manager.
-                    let db2 = db.clone();
-                    let tx2 = tx.clone();
-                    let settings2 = settings.clone();
-                    tokio::spawn(async move {
-                        if let Ok(Some(ep)) = db2.get_episode(episode_id).await
-                            && let Ok(Some(pod)) = db2.get_podcast(ep.podcast_id).await
-                        {
-                            let dm2 = crate::download_manager::DownloadManager::new(db2.clone());
-                            let folders = vec![pod.title.clone()];
-                            let file_name = ep.title.clone();
-                            let ep_title = ep.title.clone();
-                            let podcast_id2 = pod.id;
-                            let _ = db2.update_episode_download_status(episode_id, DownloadStatus::Downloading, None).await;
-                            let _ = tx2.send(AppEvent::DownloadStatusChanged { episode_id, status: DownloadStatus::Downloading, path: None });
-                            tokio::task::spawn_blocking(move || {
-                                match dm2.download(ep.url, folders, file_name) {
-                                    Ok(path) => {
-                                        let path_str = path.to_string_lossy().to_string();
-                                        let rt = tokio::runtime::Handle::current();
-                                        rt.block_on(async {
-                                            let _ = db2.update_episode_download_status(episode_id, DownloadStatus::Downloaded, Some(path_str.clone())).await;
-                                            enforce_retention_policy(podcast_id2, &db2, &dm2, &settings2).await;
-                                        });
-                                        if settings2.notify_download_complete {
-                                            send_notification("Download complete", &ep_title);
-                                        }
-                                        let _ = tx2.send(AppEvent::DownloadStatusChanged { episode_id, status: DownloadStatus::Downloaded, path: Some(path_str) });
-                                    }
-                                    Err(_) => {
-                                        let rt = tokio::runtime::Handle::current();
-                                        rt.block_on(async {
-                                            let _ = db2.update_episode_download_status(episode_id, DownloadStatus::Failed, None).await;
-                                        });
-                                        let _ = tx2.send(AppEvent::DownloadStatusChanged { episode_id, status: DownloadStatus::Failed, path: None });
-                                    }
-                                }
-                            }).await.ok();
-                        }
-                    });
+                    download_scheduler.enqueue(episode_id, true).await;
                 }
             }
 
             let _ = db.update_podcast_synced_at(podcast_id).await;
 
+            // An HTTP-level move wins over the <itunes:new-feed-url> tag.
+            if let Some(new_url) = moved_to.or(new_feed_url) {
+                follow_feed_move(&podcast, new_url, &db, &tx).await;
+            }
+
             match db.get_episodes(podcast_id).await {
                 Ok(episodes) => {
+                    let cadence = sync_scheduler::publish_interval_minutes(&episodes, now);
+                    let _ = db
+                        .update_podcast_sync_hints(podcast_id, ttl_minutes, update_minutes, cadence)
+                        .await;
                     let _ = tx.send(AppEvent::EpisodesUpdated {
                         podcast_id,
                         episodes,
@@ -1235,128 +1789,218 @@ async fn sync_podcast(
             }
         }
         Err(e) => {
-            let _ = tx.send(AppEvent::Toast(ToastMessage::error(&format!(
-                "Sync failed for '{}': {e}",
-                podcast.title
-            ))));
+            let reason = describe_sync_error(&e);
+            eprintln!("[sync] '{}' failed: {e}", podcast.title);
+            // Only the first failure is worth a toast; after that the card shows the state.
+            if let Ok(1) = db.record_sync_failure(podcast_id, reason).await {
+                let _ = tx.send(AppEvent::Toast(ToastMessage::error(&format!(
+                    "Sync failed for '{}': {e}",
+                    podcast.title
+                ))));
+            }
         }
     }
 
+    download_scheduler.apply_retention(podcast_id).await;
+
+    // Refreshes the sync time, health and any metadata or URL changes on the card.
+    if let Ok(Some(updated)) = db.get_podcast(podcast_id).await {
+        let _ = tx.send(AppEvent::PodcastUpdated(updated));
+    }
     let _ = tx.send(AppEvent::SyncCompleted(podcast_id));
 }
 
-async fn background_sync(db: Database, tx: UnboundedSender<AppEvent>) {
-    let settings = db.get_settings().await.unwrap_or_default();
-    background_sync_with_settings(db, tx, settings).await;
+// Short reason for a failed sync, shown on the podcast card ("404", "timed out").
+fn describe_sync_error(e: &anyhow::Error) -> String {
+    match e.downcast_ref::<HttpError>() {
+        Some(HttpError::Status(code)) => return code.to_string(),
+        Some(HttpError::Timeout) => return "timed out".to_string(),
+        Some(HttpError::Connect(_)) => return "could not connect".to_string(),
+        _ => {}
+    }
+    if e.is::<rss::Error>() || e.is::<quick_xml::Error>() || e.is::<serde_json::Error>() {
+        return "invalid feed".to_string();
+    }
+    let message = e.to_string();
+    match message.char_indices().nth(60) {
+        Some((end, _)) => format!("{}...", &message[..end]),
+        None => message,
+    }
 }
 
-async fn background_sync_with_settings(
-    db: Database,
-    tx: UnboundedSender<AppEvent>,
-    settings: Settings,
-) {
-    let podcasts = match db.get_all_podcasts().await {
-        Ok(p) => p,
-        Err(_) => return,
+fn http_config(settings: &Settings) -> HttpConfig {
+    let non_empty = |s: &str| {
+        let s = s.trim();
+        (!s.is_empty()).then(|| s.to_string())
     };
-    for podcast in podcasts {
-        let db = db.clone();
-        let tx = tx.clone();
-        let settings = settings.clone();
-        tokio::spawn(async move {
-            sync_podcast(podcast.id, db, tx, &settings).await;
-        });
+    HttpConfig {
+        proxy: non_empty(&settings.http_proxy),
+        user_agent: non_empty(&settings.http_user_agent),
+        connect_timeout_secs: Some(settings.http_connect_timeout_secs.max(1) as u64),
+        read_timeout_secs: Some(settings.http_read_timeout_secs.max(1) as u64),
+        ca_bundle_path: non_empty(&settings.http_ca_bundle_path),
     }
 }
 
-// Parses an RSS feed URL and returns (title, description, image_url, episodes).
-async fn fetch_feed(url: &str) -> anyhow::Result<(String, String, String, Vec<Episode>)> {
-    let body = reqwest::get(url).await?.text().await?;
-    let channel = rss::Channel::read_from(body.as_bytes())?;
-
-    let title = channel.title().to_string();
-    let description = channel.description().to_string();
-    let image_url = channel
-        .image()
-        .map(|i| i.url().to_string())
-        .or_else(|| {
-            channel
-                .itunes_ext()
-                .and_then(|e| e.image())
-                .map(|u| u.to_string())
-        })
-        .unwrap_or_default();
-
-    let now = chrono::Utc::now().timestamp();
-
-    let episodes = channel
-        .items()
-        .iter()
-        .filter_map(|item| {
-            let enclosure = item.enclosure()?;
-
-            let duration = item
-                .itunes_ext()
-                .and_then(|ext| ext.duration())
-                .and_then(|d| {
-                    let parts: Vec<&str> = d.split(':').collect();
-                    match parts.len() {
-                        3 => {
-                            let h: i64 = parts[0].parse().ok()?;
-                            let m: i64 = parts[1].parse().ok()?;
-                            let s: i64 = parts[2].parse().ok()?;
-                            Some(h * 3600 + m * 60 + s)
-                        }
-                        2 => {
-                            let m: i64 = parts[0].parse().ok()?;
-                            let s: i64 = parts[1].parse().ok()?;
-                            Some(m * 60 + s)
-                        }
-                        1 => parts[0].parse().ok(),
-                        _ => None,
-                    }
-                })
-                .unwrap_or(0);
+// The settings the global retention rules depend on.
+fn retention_config(settings: &Settings) -> (i32, i32, i32, bool) {
+    (
+        settings.global_keep_episodes_count,
+        settings.retention_played_after_days,
+        settings.retention_older_than_days,
+        settings.retention_keep_unplayed,
+    )
+}
 
-            let chapters_url = item
-                .extensions()
-                .get("podcast")
-                .and_then(|ns| ns.get("chapters"))
-                .and_then(|v| v.first())
-                .and_then(|ext| ext.attrs().get("url"))
-                .cloned();
+// HTTP cache validators, echoed back to the feed host as If-None-Match / If-Modified-Since.
+#[derive(Debug, Clone, Default)]
+struct FeedValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
 
-            Some(Episode {
-                id: 0,
-                podcast_id: 0,
-                title: item.title().unwrap_or("Untitled").to_string(),
-                description: item.description().unwrap_or("").to_string(),
-                url: enclosure.url().to_string(),
-                audio_type: enclosure.mime_type().to_string(),
-                publish_date: item
-                    .pub_date()
-                    .and_then(|d| chrono::DateTime::parse_from_rfc2822(d).ok())
-                    .map(|d| d.timestamp())
-                    .unwrap_or(0),
-                is_played: false,
-                duration,
-                position_seconds: 0.0,
-                created_at: now,
-                updated_at: now,
-                download_status: DownloadStatus::NotDownloaded,
-                downloaded_path: None,
-                speed_preset: None,
-                chapters_url,
-                total_listen_seconds: 0,
-            })
-        })
-        .collect();
+enum FeedResponse {
+    // 304 — the feed is unchanged since the validators were issued.
+    NotModified,
+    Modified(ParsedFeed, FeedValidators),
+}
 
-    Ok((title, description, image_url, episodes))
+const MAX_FEED_REDIRECTS: usize = 10;
+
+// Fetches a feed, sending any stored validators as conditional request headers.
+//
+// Redirects are followed by hand so permanent moves can be told apart from temporary ones.
+// Alongside the response, returns the URL the feed permanently moved to: the target of an
+// unbroken chain of 301/308 hops from `url`, if there was one.
+async fn fetch_feed(
+    http: &Arc<dyn HttpFetcher>,
+    url: &str,
+    validators: &FeedValidators,
+) -> anyhow::Result<(FeedResponse, Option<String>)> {
+    let http = http.clone();
+    let url = url.to_string();
+    let validators = validators.clone();
+    tokio::task::spawn_blocking(move || fetch_feed_blocking(http.as_ref(), &url, &validators))
+        .await?
+}
+
+fn fetch_feed_blocking(
+    http: &dyn HttpFetcher,
+    url: &str,
+    validators: &FeedValidators,
+) -> anyhow::Result<(FeedResponse, Option<String>)> {
+    let mut current = url::Url::parse(url)?;
+    let mut moved_to = None;
+    let mut permanent = true;
+    let mut hops = 0;
+
+    let response = loop {
+        let mut request = HttpRequest::get(RequestPurpose::Sync, current.as_str()).without_redirects();
+        if let Some(etag) = &validators.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+
+        let response = http.get(request)?;
+        let status = response.status;
+        if !(300..400).contains(&status) || status == 304 {
+            break response;
+        }
+
+        hops += 1;
+        if hops > MAX_FEED_REDIRECTS {
+            anyhow::bail!("Too many redirects");
+        }
+        let location = response
+            .header("Location")
+            .ok_or_else(|| anyhow::anyhow!("Redirect ({status}) without a Location header"))?;
+        current = current.join(location)?;
+
+        // A temporary hop anywhere in the chain means the original URL is still canonical.
+        permanent &= matches!(status, 301 | 308);
+        if permanent {
+            moved_to = Some(current.to_string());
+        }
+    };
+
+    if response.status == 304 {
+        return Ok((FeedResponse::NotModified, moved_to));
+    }
+    let response = response.error_for_status()?;
+
+    let validators = FeedValidators {
+        etag: response.header("ETag").map(str::to_string),
+        last_modified: response.header("Last-Modified").map(str::to_string),
+    };
+
+    let body = response.text()?;
+    Ok((FeedResponse::Modified(parse_feed(&body)?, validators), moved_to))
+}
+
+// Unconditional fetch for a feed we aren't subscribed to yet. Returns the URL to subscribe
+// to, which is the permanent redirect target when the feed has moved.
+async fn fetch_new_feed(
+    http: &Arc<dyn HttpFetcher>,
+    url: &str,
+) -> anyhow::Result<(ParsedFeed, FeedValidators, String)> {
+    match fetch_feed(http, url, &FeedValidators::default()).await? {
+        (FeedResponse::Modified(feed, validators), moved_to) => {
+            Ok((feed, validators, moved_to.unwrap_or_else(|| url.to_string())))
+        }
+        (FeedResponse::NotModified, _) => {
+            Err(anyhow::anyhow!("Feed host returned 304 unexpectedly"))
+        }
+    }
+}
+
+// Re-points a subscription whose feed moved permanently. When the new URL is already
+// subscribed, the UI is asked to offer a merge instead.
+async fn follow_feed_move(
+    podcast: &Podcast,
+    new_url: String,
+    db: &Database,
+    tx: &UnboundedSender<AppEvent>,
+) {
+    if new_url == podcast.url {
+        return;
+    }
+
+    match db.get_podcast_id_by_url(new_url.clone()).await {
+        Ok(Some(existing_id)) => {
+            if let Ok(Some(existing)) = db.get_podcast(existing_id).await {
+                let _ = tx.send(AppEvent::FeedMoveConflict {
+                    moved: Box::new(podcast.clone()),
+                    existing: Box::new(existing),
+                });
+            }
+        }
+        Ok(None) => match db.update_podcast_url(podcast.id, new_url.clone()).await {
+            Ok(()) => {
+                eprintln!("[sync] '{}' moved: {} -> {}", podcast.title, podcast.url, new_url);
+                let _ = tx.send(AppEvent::Toast(ToastMessage::info(&format!(
+                    "'{}' moved to a new feed address",
+                    podcast.title
+                ))));
+            }
+            Err(e) => {
+                let _ = tx.send(AppEvent::Error(format!("Failed to update feed URL: {e}")));
+            }
+        },
+        Err(e) => {
+            let _ = tx.send(AppEvent::Error(format!("Sync DB error: {e}")));
+        }
+    }
 }
 
 // OPML import
-async fn import_opml(path: std::path::PathBuf, db: Database, tx: UnboundedSender<AppEvent>) {
+async fn import_opml(
+    path: std::path::PathBuf,
+    db: Database,
+    tx: UnboundedSender<AppEvent>,
+    http: Arc<dyn HttpFetcher>,
+) {
     let raw = match std::fs::read_to_string(&path) {
         Ok(s) => s,
         Err(e) => {
@@ -1397,15 +2041,19 @@ async fn import_opml(path: std::path::PathBuf, db: Database, tx: UnboundedSender
 
         let db2 = db.clone();
         let tx2 = tx.clone();
-        match fetch_feed(&url).await {
-            Ok((title, description, image_url, episodes)) => {
+        match fetch_new_feed(&http, &url).await {
+            // Already subscribed under the address this one redirects to.
+            Ok((_, _, url)) if existing.contains(&url) => {
+                skipped += 1;
+            }
+            Ok((feed, validators, url)) => {
                 let now = chrono::Utc::now().timestamp();
                 let podcast = Podcast {
                     id: 0,
                     url: url.clone(),
-                    title,
-                    description,
-                    image_url,
+                    title: feed.title,
+                    description: feed.description,
+                    image_url: feed.image_url,
                     episode_count: 0,
                     last_synced_at: now,
                     created_at: now,
@@ -1413,13 +2061,27 @@ async fn import_opml(path: std::path::PathBuf, db: Database, tx: UnboundedSender
                     speed_preset: None,
                     auto_download: None,
                     keep_episodes_count: None,
+                    delete_played_after_days: None,
+                    delete_older_than_days: None,
+                    keep_unplayed: None,
                     skip_intro_seconds: 0,
                     skip_outro_seconds: 0,
+                    etag: validators.etag,
+                    last_modified: validators.last_modified,
+                    sync_interval_minutes: None,
+                    feed_ttl_minutes: feed.ttl_minutes,
+                    feed_update_minutes: feed.update_minutes,
+                    publish_interval_minutes: sync_scheduler::publish_interval_minutes(&feed.episodes, now),
+                    last_sync_attempt_at: Some(now),
+                    sync_failure_count: 0,
+                    sync_failing_since: None,
+                    sync_last_error: None,
                 };
 
                 match db2.insert_podcast(podcast).await {
                     Ok(saved) => {
-                        let eps: Vec<Episode> = episodes
+                        let eps: Vec<Episode> = feed
+                            .episodes
                             .into_iter()
                             .map(|mut e| {
                                 e.podcast_id = saved.id;
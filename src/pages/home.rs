@@ -278,7 +278,7 @@ impl HomePage {
             if self.pending_density.is_some() {
                 self.frames_since_density_change += 1;
                 if self.frames_since_density_change >= 10 {
-                    let _ = cmd_tx.send(AppCommand::SaveSettings(state.settings.clone()));
+                    let _ = cmd_tx.send(AppCommand::SaveSettings(Box::new(state.settings.clone())));
                     self.pending_density = None;
                 }
             }
@@ -363,6 +363,12 @@ fn render_podcast_row(
                 ),
                 t,
             ));
+
+            if !is_syncing
+                && let Some(health) = podcast.sync_health()
+            {
+                ui.label(text_meta(health, t).color(t.error));
+            }
         });
     });
 
@@ -5,10 +5,12 @@ use crate::commands::AppCommand;
 use crate::db::models::{DownloadStatus, Episode};
 use crate::design::components::*;
 use crate::design::spacing::*;
+use crate::design::tokens::ThemeTokens;
 use crate::design::typography::*;
 use crate::state::AppState;
+use crate::sync_scheduler;
 use crate::types::{Page, PodcastPreferences, SortOrder};
-use crate::utils::string_utils::strip_html;
+use crate::utils::string_utils::{format_bytes, format_days, strip_html};
 
 pub struct PodcastDetailPage {
     search_query: String,
@@ -58,8 +60,12 @@ impl PodcastDetailPage {
                 speed_preset: podcast.speed_preset,
                 auto_download: podcast.auto_download,
                 keep_episodes_count: podcast.keep_episodes_count,
+                delete_played_after_days: podcast.delete_played_after_days,
+                delete_older_than_days: podcast.delete_older_than_days,
+                keep_unplayed: podcast.keep_unplayed,
                 skip_intro_seconds: podcast.skip_intro_seconds,
                 skip_outro_seconds: podcast.skip_outro_seconds,
+                sync_interval_minutes: podcast.sync_interval_minutes,
             };
             self.prefs_loaded_for = Some(podcast.id);
             self.confirm_remove = false;
@@ -278,6 +284,83 @@ impl PodcastDetailPage {
                         });
                         ui.end_row();
 
+                        ui.label(text_label("Delete Played After:", &t));
+                        days_override(
+                            ui,
+                            &mut self.working_prefs.delete_played_after_days,
+                            state.settings.retention_played_after_days,
+                            &t,
+                        );
+                        ui.end_row();
+
+                        ui.label(text_label("Delete Downloaded After:", &t));
+                        days_override(
+                            ui,
+                            &mut self.working_prefs.delete_older_than_days,
+                            state.settings.retention_older_than_days,
+                            &t,
+                        );
+                        ui.end_row();
+
+                        ui.label(text_label("Keep Unplayed:", &t));
+                        ui.horizontal(|ui| {
+                            let use_global = self.working_prefs.keep_unplayed.is_none();
+                            let enabled = self.working_prefs.keep_unplayed.unwrap_or(false);
+                            if btn_segment(ui, "Use Global", use_global, &t).clicked() {
+                                self.working_prefs.keep_unplayed = None;
+                            }
+                            ui.add_space(SPACE_1);
+                            if btn_segment(ui, "Off", !use_global && !enabled, &t).clicked() {
+                                self.working_prefs.keep_unplayed = Some(false);
+                            }
+                            ui.add_space(SPACE_1);
+                            if btn_segment(ui, "On", !use_global && enabled, &t).clicked() {
+                                self.working_prefs.keep_unplayed = Some(true);
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label(text_label("Sync Interval:", &t));
+                        ui.horizontal(|ui| {
+                            let has_interval = self.working_prefs.sync_interval_minutes.is_some();
+                            let automatic =
+                                sync_scheduler::interval_minutes(&podcast, &state.settings);
+                            let mut interval_val =
+                                self.working_prefs.sync_interval_minutes.unwrap_or(automatic);
+                            if ui
+                                .add_enabled(
+                                    has_interval,
+                                    egui::Slider::new(
+                                        &mut interval_val,
+                                        sync_scheduler::MIN_INTERVAL_MINUTES..=24 * 60,
+                                    )
+                                    .logarithmic(true)
+                                    .custom_formatter(|v, _| format_minutes(v as i32)),
+                                )
+                                .changed()
+                            {
+                                self.working_prefs.sync_interval_minutes = Some(interval_val);
+                            }
+                            ui.add_space(SPACE_2);
+                            if has_interval {
+                                if btn_ghost(ui, "Automatic", &t)
+                                    .on_hover_text(
+                                        "Adapt to how often this show publishes, never more \
+                                         often than the global interval",
+                                    )
+                                    .clicked()
+                                {
+                                    self.working_prefs.sync_interval_minutes = None;
+                                }
+                            } else if btn_ghost(ui, "Override", &t)
+                                .on_hover_text("Set a fixed sync interval for this podcast")
+                                .clicked()
+                            {
+                                self.working_prefs.sync_interval_minutes = Some(automatic);
+                            }
+                        });
+                        ui.end_row();
+
                         ui.label(text_label("Skip Intro (sec):", &t));
                         ui.add(
                             egui::DragValue::new(&mut self.working_prefs.skip_intro_seconds)
@@ -467,9 +550,25 @@ impl PodcastDetailPage {
                                                     ui.close();
                                                 }
 
+                                                if ui
+                                                    .button(if episode.is_protected {
+                                                        "Allow Cleanup"
+                                                    } else {
+                                                        "Protect from Cleanup"
+                                                    })
+                                                    .clicked()
+                                                {
+                                                    let _ = cmd_tx.send(
+                                                        AppCommand::SetEpisodeProtected {
+                                                            episode_id: ep_id,
+                                                            protected: !episode.is_protected,
+                                                        },
+                                                    );
+                                                    ui.close();
+                                                }
+
                                                 match episode.download_status {
-                                                    DownloadStatus::NotDownloaded
-                                                    | DownloadStatus::Failed => {
+                                                    DownloadStatus::NotDownloaded => {
                                                         if ui.button("Download").clicked() {
                                                             let _ = cmd_tx.send(
                                                                 AppCommand::DownloadEpisode(ep_id),
@@ -477,11 +576,55 @@ impl PodcastDetailPage {
                                                             ui.close();
                                                         }
                                                     }
+                                                    DownloadStatus::Queued => {
+                                                        if ui.button("Cancel Download").clicked() {
+                                                            let _ = cmd_tx.send(
+                                                                AppCommand::CancelDownload(ep_id),
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                    }
                                                     DownloadStatus::Downloading => {
-                                                        ui.add_enabled(
-                                                            false,
-                                                            egui::Button::new("Downloading…"),
-                                                        );
+                                                        if ui.button("Pause Download").clicked() {
+                                                            let _ = cmd_tx.send(
+                                                                AppCommand::PauseDownload(ep_id),
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                        if ui.button("Cancel Download").clicked() {
+                                                            let _ = cmd_tx.send(
+                                                                AppCommand::CancelDownload(ep_id),
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                    }
+                                                    DownloadStatus::Paused => {
+                                                        if ui.button("Resume Download").clicked() {
+                                                            let _ = cmd_tx.send(
+                                                                AppCommand::RetryDownload(ep_id),
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                        if ui.button("Cancel Download").clicked() {
+                                                            let _ = cmd_tx.send(
+                                                                AppCommand::CancelDownload(ep_id),
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                    }
+                                                    DownloadStatus::Failed => {
+                                                        if ui.button("Retry Download").clicked() {
+                                                            let _ = cmd_tx.send(
+                                                                AppCommand::RetryDownload(ep_id),
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                        if ui.button("Cancel Download").clicked() {
+                                                            let _ = cmd_tx.send(
+                                                                AppCommand::CancelDownload(ep_id),
+                                                            );
+                                                            ui.close();
+                                                        }
                                                     }
                                                     DownloadStatus::Downloaded => {
                                                         if ui.button("Delete Download").clicked()
@@ -497,6 +640,17 @@ impl PodcastDetailPage {
 
                                         ui.add_space(CONTROL_GAP);
 
+                                        if episode.is_protected {
+                                            ui.label(
+                                                egui::RichText::new(
+                                                    egui_phosphor::regular::LOCK_SIMPLE,
+                                                )
+                                                .size(14.0)
+                                                .color(t.text_meta),
+                                            )
+                                            .on_hover_text("Protected from automatic cleanup");
+                                        }
+
                                         match episode.download_status {
                                             DownloadStatus::Downloaded => {
                                                 ui.label(
@@ -509,7 +663,31 @@ impl PodcastDetailPage {
                                                 .on_hover_text("Downloaded");
                                             }
                                             DownloadStatus::Downloading => {
-                                                ui.spinner();
+                                                match state.download_progress.get(&ep_id) {
+                                                    Some(&(bytes, Some(total))) if total > 0 => {
+                                                        let fraction = bytes as f32 / total as f32;
+                                                        ui.add(
+                                                            egui::ProgressBar::new(fraction)
+                                                                .desired_width(80.0)
+                                                                .show_percentage(),
+                                                        )
+                                                        .on_hover_text(format!(
+                                                            "{} of {}",
+                                                            format_bytes(bytes as i64),
+                                                            format_bytes(total as i64)
+                                                        ));
+                                                    }
+                                                    Some(&(bytes, _)) => {
+                                                        ui.spinner();
+                                                        ui.label(text_meta(
+                                                            format_bytes(bytes as i64),
+                                                            &t,
+                                                        ));
+                                                    }
+                                                    None => {
+                                                        ui.spinner();
+                                                    }
+                                                }
                                             }
                                             DownloadStatus::Failed => {
                                                 ui.label(
@@ -519,7 +697,32 @@ impl PodcastDetailPage {
                                                     .size(14.0)
                                                     .color(t.error),
                                                 )
-                                                .on_hover_text("Download failed");
+                                                .on_hover_text(
+                                                    match state.download_errors.get(&ep_id) {
+                                                        Some(reason) => format!("Download failed: {reason}"),
+                                                        None => "Download failed".to_string(),
+                                                    },
+                                                );
+                                            }
+                                            DownloadStatus::Queued => {
+                                                ui.label(
+                                                    egui::RichText::new(
+                                                        egui_phosphor::regular::CLOCK,
+                                                    )
+                                                    .size(14.0)
+                                                    .color(t.text_meta),
+                                                )
+                                                .on_hover_text("Queued");
+                                            }
+                                            DownloadStatus::Paused => {
+                                                ui.label(
+                                                    egui::RichText::new(
+                                                        egui_phosphor::regular::PAUSE,
+                                                    )
+                                                    .size(14.0)
+                                                    .color(t.text_meta),
+                                                )
+                                                .on_hover_text("Download paused");
                                             }
                                             DownloadStatus::NotDownloaded => {}
                                         }
@@ -584,3 +787,40 @@ impl PodcastDetailPage {
         filtered
     }
 }
+
+fn format_minutes(minutes: i32) -> String {
+    if minutes < 60 {
+        format!("{minutes}m")
+    } else if minutes % 60 == 0 {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    }
+}
+
+// Day-count slider for a retention rule, inheriting `global` until overridden. 0 = never.
+fn days_override(ui: &mut Ui, value: &mut Option<i32>, global: i32, t: &ThemeTokens) {
+    ui.horizontal(|ui| {
+        let has_value = value.is_some();
+        let mut days = value.unwrap_or(global);
+        if ui
+            .add_enabled(
+                has_value,
+                egui::Slider::new(&mut days, 0..=365)
+                    .logarithmic(true)
+                    .custom_formatter(|v, _| format_days(v as i32)),
+            )
+            .changed()
+        {
+            *value = Some(days);
+        }
+        ui.add_space(SPACE_2);
+        if has_value {
+            if btn_ghost(ui, "Use Global", t).clicked() {
+                *value = None;
+            }
+        } else if btn_ghost(ui, "Override", t).clicked() {
+            *value = Some(global);
+        }
+    });
+}
@@ -6,11 +6,13 @@ use tokio::sync::{mpsc::UnboundedSender, oneshot};
 
 use crate::commands::AppCommand;
 use crate::design::components::*;
+use crate::download_naming::{DEFAULT_TEMPLATE, PLACEHOLDERS, validate_template};
 use crate::design::typography::*;
 use crate::design::spacing::*;
-use crate::ports::{FilePicker, FolderPicker};
+use crate::ports::{FilePicker, FolderPicker, RequestPurpose};
 use crate::state::AppState;
 use crate::types::{HomeDensity, Page, Settings, ThemeMode, TrimSilenceMode};
+use crate::utils::string_utils::{format_bytes, format_days, format_time_of_day};
 
 pub struct SettingsPage {
     working: Settings,
@@ -21,6 +23,13 @@ pub struct SettingsPage {
     pending_import_path: Option<oneshot::Receiver<Option<PathBuf>>>,
     pending_export_path: Option<oneshot::Receiver<Option<PathBuf>>>,
     slider_dirty: bool,
+    // Network activity filter; the log is reloaded whenever it changes.
+    audit_purpose: Option<RequestPurpose>,
+    audit_host: String,
+    audit_requested: bool,
+    storage_requested: bool,
+    // The previous download directory, while moving its downloads is on offer.
+    move_from: Option<String>,
 }
 
 impl Default for SettingsPage {
@@ -34,6 +43,11 @@ impl Default for SettingsPage {
             pending_import_path: None,
             pending_export_path: None,
             slider_dirty: false,
+            audit_purpose: None,
+            audit_host: String::new(),
+            audit_requested: false,
+            storage_requested: false,
+            move_from: None,
         }
     }
 }
@@ -49,6 +63,18 @@ impl SettingsPage {
 
     pub fn load(&mut self, settings: Settings) {
         self.working = settings;
+        // Refresh the network activity log and disk usage each time the page is opened.
+        self.audit_requested = false;
+        self.storage_requested = false;
+        self.move_from = None;
+    }
+
+    fn request_network_audit(&mut self, cmd_tx: &UnboundedSender<AppCommand>) {
+        self.audit_requested = true;
+        let _ = cmd_tx.send(AppCommand::LoadNetworkAudit {
+            purpose: self.audit_purpose,
+            host: self.audit_host.clone(),
+        });
     }
 
     fn poll_folder_selection(&mut self) {
@@ -115,6 +141,13 @@ impl SettingsPage {
         if let Some(path) = self.poll_export_path() {
             let _ = cmd_tx.send(AppCommand::ExportOpml { path });
         }
+        if !self.audit_requested {
+            self.request_network_audit(cmd_tx);
+        }
+        if !self.storage_requested {
+            self.storage_requested = true;
+            let _ = cmd_tx.send(AppCommand::LoadStorageUsage);
+        }
 
         let t = state.theme.clone();
         let mut should_save = false;
@@ -203,6 +236,20 @@ impl SettingsPage {
 
             ui.add_space(CONTROL_GAP);
 
+            ui.horizontal(|ui| {
+                ui.label(text_label("Concurrent Syncs:", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui.add(egui::Slider::new(
+                    &mut self.working.sync_concurrency,
+                    1..=16,
+                ))
+                .on_hover_text("How many feeds are fetched at once. Feeds on the same host are always fetched one at a time.");
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
             ui.horizontal(|ui| {
                 ui.label(text_label("Auto Play Next:", &t));
                 ui.add_space(CONTROL_GAP);
@@ -211,6 +258,47 @@ impl SettingsPage {
 
             ui.add_space(CONTROL_GAP);
 
+            ui.horizontal(|ui| {
+                ui.label(text_label("Prefetch Next:", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui
+                    .add(
+                        egui::Slider::new(&mut self.working.prefetch_minutes, 0..=30)
+                            .custom_formatter(|v, _| match v as i32 {
+                                0 => "Off".to_string(),
+                                m => format!("{m} min before the end"),
+                            }),
+                    )
+                    .on_hover_text(
+                        "Fetches the next episode in the queue ahead of time, \
+                         so it starts without buffering.",
+                    );
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Prefetch Limit:", &t));
+                ui.add_space(CONTROL_GAP);
+                ui.add_enabled_ui(self.working.prefetch_minutes > 0, |ui| {
+                    let r = ui
+                        .add(
+                            egui::Slider::new(&mut self.working.prefetch_max_mb, 25..=2000)
+                                .logarithmic(true)
+                                .custom_formatter(|v, _| format_bytes(v as i64 * 1024 * 1024)),
+                        )
+                        .on_hover_text(
+                            "Larger episodes are streamed when they start instead.",
+                        );
+                    if r.changed() { self.slider_dirty = true; }
+                    if r.dragged() { any_slider_dragged = true; }
+                });
+            });
+
+            ui.add_space(CONTROL_GAP);
+
             ui.horizontal(|ui| {
                 ui.label(text_label("Home View:", &t));
                 ui.add_space(CONTROL_GAP);
@@ -247,6 +335,20 @@ impl SettingsPage {
 
             ui.add_space(CONTROL_GAP);
 
+            ui.horizontal(|ui| {
+                ui.label(text_label("Preserve Pitch:", &t));
+                ui.add_space(CONTROL_GAP);
+                should_save |= ui
+                    .checkbox(&mut self.working.preserve_pitch, "")
+                    .on_hover_text(
+                        "Keeps voices at their natural pitch when playing faster or slower. \
+                         Applies from the next episode you start.",
+                    )
+                    .changed();
+            });
+
+            ui.add_space(CONTROL_GAP);
+
             ui.horizontal(|ui| {
                 ui.label(text_label("Trim Silence:", &t));
                 ui.add_space(CONTROL_GAP);
@@ -335,6 +437,51 @@ impl SettingsPage {
                 should_save = true;
             }
 
+            if should_save
+                && self.working.download_directory != state.settings.download_directory
+                && !state.storage_usage.is_empty()
+            {
+                self.move_from
+                    .get_or_insert_with(|| state.settings.download_directory.clone());
+            }
+
+            if let Some((done, total)) = state.library_move {
+                ui.add_space(CONTROL_GAP);
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                            .desired_width(200.0),
+                    );
+                    ui.add_space(SPACE_2);
+                    ui.label(text_meta(format!("Moving downloads... {done} of {total}"), &t));
+                });
+            } else if let Some(from) = self.move_from.clone()
+                && from != self.working.download_directory
+            {
+                ui.add_space(CONTROL_GAP);
+                ui.label(text_body(format!("Existing downloads are still in {from}."), &t));
+                ui.add_space(SPACE_1);
+                ui.horizontal(|ui| {
+                    if btn_secondary(ui, "Move Them Here", &t)
+                        .on_hover_text(
+                            "Move downloads to the new directory. If this is interrupted, it \
+                             carries on the next time RCast starts.",
+                        )
+                        .clicked()
+                    {
+                        let _ = cmd_tx.send(AppCommand::MoveLibrary {
+                            from,
+                            to: self.working.download_directory.clone(),
+                        });
+                        self.move_from = None;
+                    }
+                    ui.add_space(SPACE_1);
+                    if btn_ghost(ui, "Leave Them", &t).clicked() {
+                        self.move_from = None;
+                    }
+                });
+            }
+
             ui.add_space(CONTROL_GAP);
 
             should_save |= ui
@@ -346,6 +493,70 @@ impl SettingsPage {
 
             ui.add_space(CONTROL_GAP);
 
+            ui.horizontal(|ui| {
+                should_save |= ui
+                    .checkbox(&mut self.working.auto_download_window, "Only between")
+                    .on_hover_text(
+                        "Automatic downloads wait for this time of day. \
+                         Downloads you start yourself begin right away.",
+                    )
+                    .changed();
+                ui.add_enabled_ui(self.working.auto_download_window, |ui| {
+                    for (i, minutes) in [
+                        &mut self.working.auto_download_window_start,
+                        &mut self.working.auto_download_window_end,
+                    ]
+                    .into_iter()
+                    .enumerate()
+                    {
+                        if i > 0 {
+                            ui.label(text_body("and", &t));
+                        }
+                        let r = ui.add(
+                            egui::Slider::new(minutes, 0..=24 * 60 - 15)
+                                .step_by(15.0)
+                                .custom_formatter(|v, _| format_time_of_day(v as i32)),
+                        );
+                        if r.changed() { self.slider_dirty = true; }
+                        if r.dragged() { any_slider_dragged = true; }
+                    }
+                });
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Bandwidth Limit:", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui
+                    .add(
+                        egui::Slider::new(&mut self.working.download_bandwidth_kbps, 0..=20 * 1024)
+                            .logarithmic(true)
+                            .custom_formatter(|v, _| {
+                                let kbps = v as i64;
+                                if kbps == 0 {
+                                    "No limit".to_string()
+                                } else {
+                                    format!("{}/s", format_bytes(kbps * 1024))
+                                }
+                            }),
+                    )
+                    .on_hover_text("Combined speed of all automatic downloads.");
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            should_save |= ui
+                .checkbox(
+                    &mut self.working.limit_manual_downloads,
+                    "Also limit downloads I start myself",
+                )
+                .changed();
+
+            ui.add_space(CONTROL_GAP);
+
             ui.horizontal(|ui| {
                 ui.label(text_label("Keep Episodes:", &t));
                 ui.add_space(CONTROL_GAP);
@@ -363,6 +574,355 @@ impl SettingsPage {
                 if r.dragged() { any_slider_dragged = true; }
             });
 
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Delete Played After:", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui
+                    .add(
+                        egui::Slider::new(&mut self.working.retention_played_after_days, 0..=365)
+                            .logarithmic(true)
+                            .custom_formatter(|v, _| format_days(v as i32)),
+                    )
+                    .on_hover_text("Delete a download this many days after it was marked played.");
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Delete Downloaded After:", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui
+                    .add(
+                        egui::Slider::new(&mut self.working.retention_older_than_days, 0..=365)
+                            .logarithmic(true)
+                            .custom_formatter(|v, _| format_days(v as i32)),
+                    )
+                    .on_hover_text("Delete downloads this many days after they finished.");
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            should_save |= ui
+                .checkbox(
+                    &mut self.working.retention_keep_unplayed,
+                    "Never delete unplayed downloads",
+                )
+                .on_hover_text(
+                    "Unplayed downloads are kept regardless of the keep count and age. \
+                     Protected episodes are always kept.",
+                )
+                .changed();
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Concurrent Downloads:", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui.add(egui::Slider::new(
+                    &mut self.working.download_concurrency,
+                    1..=8,
+                ))
+                .on_hover_text("How many episodes download at once. The rest wait in the queue.");
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            let template_check = validate_template(&self.working.download_name_template);
+            ui.horizontal(|ui| {
+                ui.label(text_label("File Names:", &t));
+                ui.add_space(CONTROL_GAP);
+                let placeholders = PLACEHOLDERS
+                    .iter()
+                    .map(|(name, what)| format!("{{{name}}}  {what}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let r = ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.working.download_name_template)
+                            .desired_width(280.0)
+                            .hint_text(DEFAULT_TEMPLATE),
+                    )
+                    .on_hover_text(format!("Use / for folders. Placeholders:\n{placeholders}"));
+                should_save |= r.lost_focus() && template_check.is_ok();
+            });
+            if let Err(e) = &template_check {
+                ui.add_space(SPACE_1);
+                ui.label(text_meta(e, &t).color(t.error));
+            }
+
+            ui.add_space(SPACE_1);
+            let template_saved = template_check.is_ok()
+                && self.working.download_name_template == state.settings.download_name_template;
+            if ui
+                .add_enabled_ui(template_saved, |ui| {
+                    btn_secondary(ui, "Rename Existing Downloads", &t)
+                })
+                .inner
+                .on_hover_text("Move every downloaded file to match the template above.")
+                .clicked()
+            {
+                let _ = cmd_tx.send(AppCommand::RenameDownloads);
+            }
+
+            ui.add_space(CONTROL_GAP);
+
+            should_save |= ui
+                .checkbox(&mut self.working.write_download_tags, "Tag downloaded files")
+                .on_hover_text(
+                    "Write the episode title, podcast name, date, artwork and chapters into \
+                     downloaded MP3 and M4A files, so other players can show them.",
+                )
+                .changed();
+
+            ui.add_space(CONTROL_GAP);
+
+            should_save |= ui
+                .checkbox(
+                    &mut self.working.strip_tracking_prefixes,
+                    "Skip tracking redirects",
+                )
+                .on_hover_text(
+                    "Fetch audio straight from the podcast host instead of through analytics \
+                     services like Podtrac or Chartable. Falls back to the original link if \
+                     the direct one fails.",
+                )
+                .changed();
+
+            if self.working.strip_tracking_prefixes {
+                ui.add_space(SPACE_1);
+                ui.label(text_meta(
+                    "Extra prefixes to skip, one per line (e.g. tracker.example.com/t/*)",
+                    &t,
+                ));
+                should_save |= ui
+                    .add(
+                        egui::TextEdit::multiline(&mut self.working.tracking_prefix_rules)
+                            .desired_rows(3)
+                            .hint_text("host/path/*"),
+                    )
+                    .lost_focus();
+            }
+
+            // ── Storage ──────────────────────────────────────────────────────
+            section_header(ui, "Storage", &t);
+
+            let used: u64 = state.storage_usage.iter().map(|p| p.bytes).sum();
+            let played: u64 = state.storage_usage.iter().map(|p| p.played_bytes).sum();
+            ui.label(text_body(
+                match self.working.download_quota_bytes() {
+                    Some(quota) => format!(
+                        "Downloads use {} of {}.",
+                        format_bytes(used as i64),
+                        format_bytes(quota as i64)
+                    ),
+                    None => format!("Downloads use {}.", format_bytes(used as i64)),
+                },
+                &t,
+            ));
+            ui.add_space(SPACE_2);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Storage Limit:", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui
+                    .add(
+                        egui::Slider::new(&mut self.working.download_quota_gb, 0..=500)
+                            .custom_formatter(|v, _| {
+                                if v == 0.0 { "No limit".to_string() } else { format!("{} GB", v as i32) }
+                            }),
+                    )
+                    .on_hover_text(
+                        "When a download would go over this, the oldest played downloads are \
+                         deleted to make room.",
+                    );
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                if btn_secondary(ui, "Refresh", &t).clicked() {
+                    let _ = cmd_tx.send(AppCommand::LoadStorageUsage);
+                }
+                ui.add_space(SPACE_1);
+                if btn_secondary(ui, "Check Downloads", &t)
+                    .on_hover_text(
+                        "Find downloads whose files are gone and files that belong to no episode",
+                    )
+                    .clicked()
+                {
+                    let _ = cmd_tx.send(AppCommand::ReconcileDownloads);
+                }
+                ui.add_space(SPACE_1);
+                if ui
+                    .add_enabled_ui(played > 0, |ui| {
+                        btn_destructive(ui, "Delete All Played Downloads", &t)
+                    })
+                    .inner
+                    .clicked()
+                {
+                    let _ = cmd_tx.send(AppCommand::DeletePlayedDownloads(None));
+                }
+            });
+
+            if !state.storage_usage.is_empty() {
+                ui.add_space(SPACE_2);
+                egui::Grid::new("storage_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .spacing([SPACE_4, SPACE_1])
+                    .show(ui, |ui| {
+                        for podcast in &state.storage_usage {
+                            ui.label(text_body(&podcast.podcast_title, &t));
+                            ui.label(text_meta(
+                                format!(
+                                    "{} file{}",
+                                    podcast.files,
+                                    if podcast.files == 1 { "" } else { "s" }
+                                ),
+                                &t,
+                            ));
+                            ui.label(text_meta(format_bytes(podcast.bytes as i64), &t))
+                                .on_hover_text(format!(
+                                    "{} played",
+                                    format_bytes(podcast.played_bytes as i64)
+                                ));
+                            if ui
+                                .add_enabled_ui(podcast.played_bytes > 0, |ui| {
+                                    btn_secondary(ui, "Delete Played", &t)
+                                })
+                                .inner
+                                .clicked()
+                            {
+                                let _ = cmd_tx.send(AppCommand::DeletePlayedDownloads(Some(
+                                    podcast.podcast_id,
+                                )));
+                            }
+                            if btn_destructive(ui, "Delete All", &t).clicked() {
+                                let _ = cmd_tx
+                                    .send(AppCommand::DeletePodcastDownloads(podcast.podcast_id));
+                            }
+                            ui.end_row();
+                        }
+                    });
+            }
+
+            if !state.held_missing_downloads.is_empty() {
+                let count = state.held_missing_downloads.len();
+                ui.add_space(SPACE_2);
+                ui.label(text_body(
+                    format!(
+                        "The files of {count} download{} could not be found. If the download \
+                         folder is on a drive that isn't connected, connect it and check again.",
+                        if count == 1 { "" } else { "s" }
+                    ),
+                    &t,
+                ));
+                ui.add_space(SPACE_1);
+                if btn_destructive(ui, "Mark as Not Downloaded", &t).clicked() {
+                    let _ = cmd_tx.send(AppCommand::ClearMissingDownloads);
+                }
+            }
+
+            if !state.unknown_downloads.is_empty() {
+                let bytes: u64 = state.unknown_downloads.iter().map(|f| f.bytes).sum();
+                let count = state.unknown_downloads.len();
+                ui.add_space(SPACE_2);
+                ui.label(text_body(
+                    format!(
+                        "{count} file{} ({}) in the download folder belong to no episode.",
+                        if count == 1 { "" } else { "s" },
+                        format_bytes(bytes as i64)
+                    ),
+                    &t,
+                ));
+                egui::CollapsingHeader::new("Unknown files")
+                    .id_salt("unknown_downloads")
+                    .show(ui, |ui| {
+                        for file in &state.unknown_downloads {
+                            ui.horizontal(|ui| {
+                                ui.label(text_meta(file.path.to_string_lossy(), &t));
+                                ui.label(text_meta(format_bytes(file.bytes as i64), &t));
+                            });
+                        }
+                    });
+                ui.add_space(SPACE_1);
+                if btn_destructive(ui, "Delete Unknown Files", &t).clicked() {
+                    let paths = state.unknown_downloads.iter().map(|f| f.path.clone()).collect();
+                    let _ = cmd_tx.send(AppCommand::DeleteUnknownFiles(paths));
+                }
+            }
+
+            ui.add_space(SPACE_2);
+            let cache = state.audio_cache;
+            ui.label(text_body(
+                format!(
+                    "Audio cache: {} in memory, {} on disk ({} episode{}).",
+                    format_bytes(cache.memory_bytes as i64),
+                    format_bytes(cache.disk_bytes as i64),
+                    cache.memory_entries + cache.disk_entries,
+                    if cache.memory_entries + cache.disk_entries == 1 { "" } else { "s" }
+                ),
+                &t,
+            ));
+            ui.add_space(SPACE_1);
+
+            for (label, value, range, hover) in [
+                (
+                    "Cache Memory:",
+                    &mut self.working.audio_cache_memory_mb,
+                    16..=2048,
+                    "Prefetched audio kept in memory before older entries move to disk.",
+                ),
+                (
+                    "Cache Disk Space:",
+                    &mut self.working.audio_cache_disk_mb,
+                    0..=16 * 1024,
+                    "Audio moved out of memory is kept in a temporary folder up to this size. \
+                     The folder is emptied whenever RCast starts.",
+                ),
+            ] {
+                ui.horizontal(|ui| {
+                    ui.label(text_label(label, &t));
+                    ui.add_space(CONTROL_GAP);
+                    let r = ui
+                        .add(
+                            egui::Slider::new(value, range)
+                                .logarithmic(true)
+                                .custom_formatter(|v, _| {
+                                    if v == 0.0 {
+                                        "Off".to_string()
+                                    } else {
+                                        format_bytes(v as i64 * 1024 * 1024)
+                                    }
+                                }),
+                        )
+                        .on_hover_text(hover);
+                    if r.changed() { self.slider_dirty = true; }
+                    if r.dragged() { any_slider_dragged = true; }
+                });
+                ui.add_space(CONTROL_GAP);
+            }
+
+            if ui
+                .add_enabled_ui(cache.memory_entries + cache.disk_entries > 0, |ui| {
+                    btn_destructive(ui, "Clear Audio Cache", &t)
+                })
+                .inner
+                .clicked()
+            {
+                let _ = cmd_tx.send(AppCommand::ClearAudioCache);
+            }
+
             // ── Subscriptions ────────────────────────────────────────────────
             section_header(ui, "Subscriptions", &t);
 
@@ -425,6 +985,88 @@ impl SettingsPage {
                 }
             });
 
+            // ── Network ──────────────────────────────────────────────────────
+            section_header(ui, "Network", &t);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Proxy:", &t));
+                ui.add_space(CONTROL_GAP);
+                should_save |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.working.http_proxy)
+                            .hint_text("socks5h://host:port or http://host:port"),
+                    )
+                    .lost_focus();
+                ui.add_space(SPACE_2);
+                if ui
+                    .add(
+                        egui::Button::new(egui::RichText::new("Use Tor").color(t.text_primary))
+                            .fill(Color32::TRANSPARENT)
+                            .stroke(Stroke::new(1.0, t.border)),
+                    )
+                    .on_hover_text("Route all traffic through a local Tor daemon (port 9050)")
+                    .clicked()
+                {
+                    self.working.http_proxy = "socks5h://127.0.0.1:9050".to_string();
+                    should_save = true;
+                }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("User Agent:", &t));
+                ui.add_space(CONTROL_GAP);
+                should_save |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.working.http_user_agent)
+                            .hint_text(concat!("RCast/", env!("CARGO_PKG_VERSION"))),
+                    )
+                    .lost_focus();
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Connect Timeout (s):", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui.add(egui::Slider::new(
+                    &mut self.working.http_connect_timeout_secs,
+                    1..=120,
+                ));
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Read Timeout (s):", &t));
+                ui.add_space(CONTROL_GAP);
+                let r = ui
+                    .add(egui::Slider::new(
+                        &mut self.working.http_read_timeout_secs,
+                        5..=300,
+                    ))
+                    .on_hover_text("How long to wait for more data before giving up on a stalled request.");
+                if r.changed() { self.slider_dirty = true; }
+                if r.dragged() { any_slider_dragged = true; }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("CA Bundle:", &t));
+                ui.add_space(CONTROL_GAP);
+                should_save |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.working.http_ca_bundle_path)
+                            .hint_text("Path to a PEM file (optional)"),
+                    )
+                    .on_hover_text("Extra root certificates to trust, e.g. for a corporate proxy.")
+                    .lost_focus();
+            });
+
             // ── Notifications ────────────────────────────────────────────────
             section_header(ui, "Notifications", &t);
 
@@ -517,6 +1159,97 @@ impl SettingsPage {
                     }
                 });
 
+            // ── Network Activity ─────────────────────────────────────────────
+            section_header(ui, "Network Activity", &t);
+
+            ui.label(text_body(
+                "Every request RCast has made. Nothing is sent anywhere except the hosts listed here.",
+                &t,
+            ));
+            ui.add_space(SPACE_2);
+
+            let mut reload_audit = false;
+            ui.horizontal_wrapped(|ui| {
+                if btn_segment(ui, "All", self.audit_purpose.is_none(), &t).clicked() {
+                    self.audit_purpose = None;
+                    reload_audit = true;
+                }
+                for purpose in RequestPurpose::ALL {
+                    ui.add_space(SPACE_1);
+                    if btn_segment(ui, purpose.label(), self.audit_purpose == Some(purpose), &t)
+                        .clicked()
+                    {
+                        self.audit_purpose = Some(purpose);
+                        reload_audit = true;
+                    }
+                }
+            });
+
+            ui.add_space(CONTROL_GAP);
+
+            ui.horizontal(|ui| {
+                ui.label(text_label("Host:", &t));
+                ui.add_space(CONTROL_GAP);
+                reload_audit |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.audit_host)
+                            .hint_text("Filter by host"),
+                    )
+                    .changed();
+                ui.add_space(SPACE_2);
+                reload_audit |= btn_secondary(ui, "Refresh", &t).clicked();
+                ui.add_space(SPACE_1);
+                if btn_destructive(ui, "Clear Log", &t).clicked() {
+                    let _ = cmd_tx.send(AppCommand::ClearNetworkAudit);
+                }
+            });
+
+            if reload_audit {
+                self.request_network_audit(cmd_tx);
+            }
+
+            ui.add_space(SPACE_2);
+
+            let entries = &state.network_audit;
+            let total_bytes: i64 = entries.iter().map(|e| e.bytes).sum();
+            ui.label(text_meta(
+                format!(
+                    "{} request{} · {}",
+                    entries.len(),
+                    if entries.len() == 1 { "" } else { "s" },
+                    format_bytes(total_bytes)
+                ),
+                &t,
+            ));
+            ui.add_space(SPACE_1);
+
+            egui::ScrollArea::vertical()
+                .id_salt("network_audit_scroll")
+                .max_height(280.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("network_audit_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .spacing([SPACE_4, SPACE_1])
+                        .show(ui, |ui| {
+                            for entry in entries {
+                                let time = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+                                    .map(|dt| {
+                                        dt.with_timezone(&chrono::Local)
+                                            .format("%Y-%m-%d %H:%M:%S")
+                                            .to_string()
+                                    })
+                                    .unwrap_or_default();
+                                ui.label(text_meta(time, &t));
+                                ui.label(text_meta(entry.purpose.label(), &t));
+                                ui.label(text_label(&entry.host, &t));
+                                ui.label(text_meta(format_bytes(entry.bytes), &t));
+                                ui.label(text_meta(&entry.result, &t));
+                                ui.end_row();
+                            }
+                        });
+                });
+
             ui.add_space(SPACE_6);
         });
 
@@ -527,7 +1260,7 @@ impl SettingsPage {
 
         if should_save {
             state.settings = self.working.clone();
-            let _ = cmd_tx.send(AppCommand::SaveSettings(self.working.clone()));
+            let _ = cmd_tx.send(AppCommand::SaveSettings(Box::new(self.working.clone())));
         }
     }
 }
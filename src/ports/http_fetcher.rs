@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+
+// Single entry point for outbound HTTP: feeds, chapters, artwork and audio.
+pub trait HttpFetcher: Send + Sync {
+    /**
+        Sends a GET request with the active network settings applied.
+        Blocks until the response headers arrive; async callers go through `spawn_blocking`.
+    */
+    fn get(&self, request: HttpRequest) -> Result<HttpResponse, HttpError>;
+
+    /**
+        Replaces the active network settings. Requests already in flight keep the old ones.
+        Returns an error (and keeps the old settings) if the proxy or CA bundle is invalid.
+    */
+    fn configure(&self, config: HttpConfig) -> Result<(), HttpError>;
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HttpConfig {
+    // e.g. `http://proxy:3128`, `socks5h://127.0.0.1:9050` (Tor). None = direct.
+    pub proxy: Option<String>,
+    pub user_agent: Option<String>,
+    pub connect_timeout_secs: Option<u64>,
+    // Maximum wait for the next chunk of a response, not for the whole body.
+    pub read_timeout_secs: Option<u64>,
+    // PEM file of extra root certificates, trusted alongside the system ones.
+    pub ca_bundle_path: Option<String>,
+}
+
+// Why a request was made. Recorded in the network audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestPurpose {
+    Sync,
+    Download,
+    Image,
+    Chapters,
+    Stream,
+}
+
+impl RequestPurpose {
+    pub const ALL: [Self; 5] = [
+        Self::Sync,
+        Self::Download,
+        Self::Image,
+        Self::Chapters,
+        Self::Stream,
+    ];
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "download" => Self::Download,
+            "image" => Self::Image,
+            "chapters" => Self::Chapters,
+            "stream" => Self::Stream,
+            _ => Self::Sync,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sync => "sync",
+            Self::Download => "download",
+            Self::Image => "image",
+            Self::Chapters => "chapters",
+            Self::Stream => "stream",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sync => "Sync",
+            Self::Download => "Download",
+            Self::Image => "Image",
+            Self::Chapters => "Chapters",
+            Self::Stream => "Stream",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub url: String,
+    pub purpose: RequestPurpose,
+    pub headers: Vec<(String, String)>,
+    pub follow_redirects: bool,
+}
+
+impl HttpRequest {
+    pub fn get(purpose: RequestPurpose, url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            purpose,
+            headers: Vec::new(),
+            follow_redirects: true,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    // Returns 3xx responses as-is so the caller can inspect each hop.
+    pub fn without_redirects(mut self) -> Self {
+        self.follow_redirects = false;
+        self
+    }
+}
+
+pub struct HttpResponse {
+    pub status: u16,
+    // Keys are lowercase.
+    pub headers: HashMap<String, String>,
+    pub body: Box<dyn Read + Send>,
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn error_for_status(self) -> Result<Self, HttpError> {
+        if self.status >= 400 {
+            Err(HttpError::Status(self.status))
+        } else {
+            Ok(self)
+        }
+    }
+
+    pub fn bytes(mut self) -> Result<Vec<u8>, HttpError> {
+        let mut buf = Vec::new();
+        self.body.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn text(self) -> Result<String, HttpError> {
+        Ok(String::from_utf8_lossy(&self.bytes()?).into_owned())
+    }
+}
+
+#[derive(Debug)]
+pub enum HttpError {
+    Status(u16),
+    Timeout,
+    Connect(String),
+    Config(String),
+    Other(String),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status(code) => write!(f, "HTTP {code}"),
+            Self::Timeout => write!(f, "Request timed out"),
+            Self::Connect(e) => write!(f, "Could not connect: {e}"),
+            Self::Config(e) => write!(f, "Invalid network settings: {e}"),
+            Self::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<std::io::Error> for HttpError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::TimedOut => Self::Timeout,
+            _ => Self::Other(e.to_string()),
+        }
+    }
+}
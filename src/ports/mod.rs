@@ -1,5 +1,9 @@
 pub mod file_picker;
 pub mod folder_picker;
+pub mod http_fetcher;
 
 pub use file_picker::FilePicker;
 pub use folder_picker::FolderPicker;
+pub use http_fetcher::{
+    HttpConfig, HttpError, HttpFetcher, HttpRequest, HttpResponse, RequestPurpose,
+};
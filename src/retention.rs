@@ -0,0 +1,120 @@
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::db::Database;
+use crate::db::models::{DownloadStatus, Episode, Podcast};
+use crate::download_manager::DownloadManager;
+use crate::events::AppEvent;
+use crate::types::Settings;
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// When a podcast's downloads are cleaned up, after resolving its overrides against the
+/// global settings. A value of 0 turns a rule off.
+///
+/// A download is deleted as soon as any rule matches it. Protected episodes are never
+/// deleted, and with `keep_unplayed` neither are unplayed ones.
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionRules {
+    // Newest downloads to keep.
+    pub keep_count: i32,
+    // Days after being marked played.
+    pub played_after_days: i32,
+    // Days after the file was downloaded.
+    pub older_than_days: i32,
+    pub keep_unplayed: bool,
+}
+
+impl RetentionRules {
+    pub fn for_podcast(podcast: &Podcast, settings: &Settings) -> Self {
+        Self {
+            keep_count: podcast
+                .keep_episodes_count
+                .unwrap_or(settings.global_keep_episodes_count),
+            played_after_days: podcast
+                .delete_played_after_days
+                .unwrap_or(settings.retention_played_after_days),
+            older_than_days: podcast
+                .delete_older_than_days
+                .unwrap_or(settings.retention_older_than_days),
+            keep_unplayed: podcast
+                .keep_unplayed
+                .unwrap_or(settings.retention_keep_unplayed),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.keep_count > 0 || self.played_after_days > 0 || self.older_than_days > 0
+    }
+
+    // The downloads to delete. `downloaded` must be ordered newest first.
+    pub fn expired<'a>(&self, downloaded: &'a [Episode], now: i64) -> Vec<&'a Episode> {
+        downloaded
+            .iter()
+            .filter(|ep| !ep.is_protected)
+            .enumerate()
+            .filter(|(rank, ep)| self.is_expired(ep, *rank, now))
+            .map(|(_, ep)| ep)
+            .collect()
+    }
+
+    // `rank` is the episode's position among the podcast's unprotected downloads.
+    fn is_expired(&self, episode: &Episode, rank: usize, now: i64) -> bool {
+        if self.keep_unplayed && !episode.is_played {
+            return false;
+        }
+        if self.keep_count > 0 && rank >= self.keep_count as usize {
+            return true;
+        }
+        if self.played_after_days > 0
+            && episode.is_played
+            && let Some(played_at) = episode.played_at
+            && now - played_at >= self.played_after_days as i64 * DAY_SECS
+        {
+            return true;
+        }
+        self.older_than_days > 0
+            && episode
+                .downloaded_at
+                .is_some_and(|at| now - at >= self.older_than_days as i64 * DAY_SECS)
+    }
+}
+
+// Deletes the downloads of a podcast that its retention rules no longer keep.
+pub async fn enforce_retention_policy(
+    podcast_id: i32,
+    db: &Database,
+    dm: &DownloadManager,
+    settings: &Settings,
+    event_tx: &UnboundedSender<AppEvent>,
+) {
+    let podcast = match db.get_podcast(podcast_id).await {
+        Ok(Some(p)) => p,
+        _ => return,
+    };
+
+    let rules = RetentionRules::for_podcast(&podcast, settings);
+    if !rules.is_enabled() {
+        return;
+    }
+
+    let downloaded = match db.get_downloaded_episodes(podcast_id).await {
+        Ok(eps) => eps,
+        Err(_) => return,
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    for ep in rules.expired(&downloaded, now) {
+        if let Some(path) = &ep.downloaded_path {
+            dm.delete_file(path).ok();
+        }
+        db.update_episode_download_status(ep.id, DownloadStatus::NotDownloaded, None)
+            .await
+            .ok();
+        let _ = event_tx.send(AppEvent::DownloadStatusChanged {
+            episode_id: ep.id,
+            status: DownloadStatus::NotDownloaded,
+            path: None,
+            reason: None,
+        });
+    }
+}
@@ -5,7 +5,7 @@ use crate::db::models::{Episode, Podcast};
 use crate::design::ThemeTokens;
 use crate::image_cache::ImageCache;
 use crate::types::{QueueDisplayItem, Settings};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub struct NowPlaying {
     pub episode_id: i32,
@@ -45,6 +45,12 @@ pub struct AppState {
     // IDs of podcasts currently being synced — used to show spinners on cards.
     pub syncing_podcast_ids: HashSet<i32>,
 
+    // Downloads
+    // Bytes received and total size for episodes currently downloading.
+    pub download_progress: HashMap<i32, (u64, Option<u64>)>,
+    // Why the last attempt failed, for episodes in the Failed state.
+    pub download_errors: HashMap<i32, String>,
+
     // Toasts
     pub toasts: ToastQueue,
 
@@ -55,6 +61,17 @@ pub struct AppState {
 
     // Statistics
     pub listening_stats: Option<crate::db::models::ListeningStats>,
+    pub network_audit: Vec<crate::db::models::NetworkAuditEntry>,
+
+    // Storage
+    pub storage_usage: Vec<crate::types::PodcastStorage>,
+    // Files in the download folder that no episode accounts for.
+    pub unknown_downloads: Vec<crate::types::UnknownFile>,
+    // Downloads whose files are gone, waiting for the user to confirm they really are.
+    pub held_missing_downloads: Vec<i32>,
+    pub audio_cache: crate::types::AudioCacheStats,
+    // Files handled and total while downloads are being moved to a new directory.
+    pub library_move: Option<(usize, usize)>,
 }
 
 impl Default for AppState {
@@ -74,10 +91,18 @@ impl Default for AppState {
             notes_podcast_bookmarks: Vec::new(),
             notes_open_request: None,
             syncing_podcast_ids: HashSet::new(),
+            download_progress: HashMap::new(),
+            download_errors: HashMap::new(),
             toasts: ToastQueue::default(),
             open_add_podcast_requested: false,
             sleep_timer_ends_at: None,
             listening_stats: None,
+            network_audit: Vec::new(),
+            storage_usage: Vec::new(),
+            unknown_downloads: Vec::new(),
+            held_missing_downloads: Vec::new(),
+            audio_cache: Default::default(),
+            library_move: None,
         }
     }
 }
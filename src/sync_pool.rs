@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+// Minimum spacing between two requests to the same host.
+const HOST_GAP: Duration = Duration::from_secs(2);
+
+// Held for the duration of a sync against a host; records when its last request finished.
+type HostSlot = Arc<tokio::sync::Mutex<Option<Instant>>>;
+
+// The current concurrency limit.
+struct Limit {
+    size: usize,
+    // Permits held by running syncs that are withdrawn, rather than returned, when those
+    // syncs finish, after the limit was lowered.
+    owed: usize,
+}
+
+#[derive(Default)]
+struct Host {
+    slot: HostSlot,
+    // Syncs queued or running against this host.
+    queued: usize,
+}
+
+/// Runs feed syncs with a global concurrency limit and per-host politeness.
+///
+/// Syncs against the same host run one at a time with [`HOST_GAP`] between them, so a
+/// library with dozens of shows on one hosting provider doesn't hammer it. A podcast that
+/// is already queued or running is not queued again.
+#[derive(Clone)]
+pub struct SyncPool {
+    permits: Arc<Semaphore>,
+    limit: Arc<Mutex<Limit>>,
+    hosts: Arc<Mutex<HashMap<String, Host>>>,
+    in_flight: Arc<Mutex<HashSet<i32>>>,
+}
+
+impl SyncPool {
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(concurrency.max(1))),
+            limit: Arc::new(Mutex::new(Limit {
+                size: concurrency.max(1),
+                owed: 0,
+            })),
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+            in_flight: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    // Running syncs keep their permits; when the limit shrinks below them, it takes effect
+    // as they finish.
+    pub fn set_concurrency(&self, concurrency: usize) {
+        let size = concurrency.max(1);
+        let mut limit = self.limit.lock().unwrap();
+        if size > limit.size {
+            let grow = size - limit.size;
+            let repaid = grow.min(limit.owed);
+            limit.owed -= repaid;
+            self.permits.add_permits(grow - repaid);
+        } else {
+            let shrink = limit.size - size;
+            limit.owed += shrink - self.permits.forget_permits(shrink);
+        }
+        limit.size = size;
+    }
+
+    // Queues `job` for `podcast_id`. Returns false if that podcast is already queued.
+    pub fn spawn<F>(&self, podcast_id: i32, feed_url: &str, job: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if !self.in_flight.lock().unwrap().insert(podcast_id) {
+            return false;
+        }
+
+        let host = url::Url::parse(feed_url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_lowercase()))
+            .unwrap_or_default();
+        let host_slot = {
+            let mut hosts = self.hosts.lock().unwrap();
+            // Forget idle hosts once their gap has passed, so the map doesn't keep every host
+            // ever synced.
+            hosts.retain(|_, h| {
+                h.queued > 0
+                    || h.slot
+                        .try_lock()
+                        .map_or(true, |last| last.is_some_and(|t| t.elapsed() < HOST_GAP))
+            });
+            let entry = hosts.entry(host.clone()).or_default();
+            entry.queued += 1;
+            entry.slot.clone()
+        };
+        let permits = self.permits.clone();
+        // Released when the task ends, even if the job panics.
+        let claim = Claim {
+            pool: self.clone(),
+            podcast_id,
+            host,
+            permit: None,
+        };
+
+        tokio::spawn(async move {
+            let mut claim = claim;
+            // Host first, so syncs queued behind a busy host don't hold global permits.
+            let mut last_request = host_slot.lock().await;
+            if let Some(last) = *last_request {
+                tokio::time::sleep_until(last + HOST_GAP).await;
+            }
+            if let Ok(permit) = permits.acquire_owned().await {
+                claim.permit = Some(permit);
+                job.await;
+            }
+            *last_request = Some(Instant::now());
+        });
+        true
+    }
+}
+
+// A queued sync's hold on its podcast and host, and on its permit once it runs.
+struct Claim {
+    pool: SyncPool,
+    podcast_id: i32,
+    host: String,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            let mut limit = self.pool.limit.lock().unwrap();
+            if limit.owed > 0 {
+                limit.owed -= 1;
+                permit.forget();
+            }
+        }
+        self.pool.in_flight.lock().unwrap().remove(&self.podcast_id);
+        if let Some(host) = self.pool.hosts.lock().unwrap().get_mut(&self.host) {
+            host.queued = host.queued.saturating_sub(1);
+        }
+    }
+}
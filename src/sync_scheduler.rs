@@ -0,0 +1,86 @@
+use crate::db::models::{Episode, Podcast};
+use crate::types::Settings;
+
+pub const MIN_INTERVAL_MINUTES: i32 = 5;
+pub const MAX_INTERVAL_MINUTES: i32 = 12 * 60;
+
+// How many polls to spread across the expected gap between two releases.
+const POLLS_PER_RELEASE: i32 = 6;
+
+// Recent episodes considered when measuring a show's publishing cadence.
+const CADENCE_SAMPLE: usize = 10;
+
+/// How often a podcast is polled in the background.
+///
+/// The global interval is the floor for adaptive scheduling: a show that publishes daily is
+/// polled a few times a day, a dormant one twice a day, but nothing is polled more often than
+/// the user asked for. A per-show override replaces the adaptive part, and the feed's `<ttl>`
+/// is always honoured as a minimum.
+pub fn interval_minutes(podcast: &Podcast, settings: &Settings) -> i32 {
+    let global = settings.sync_interval_minutes.max(MIN_INTERVAL_MINUTES);
+
+    let interval = match podcast.sync_interval_minutes {
+        Some(minutes) => minutes,
+        None => {
+            // Trust whichever signal suggests the shorter gap, so a wrong hint can only make
+            // us poll as often as before, never miss releases.
+            let release_gap = match (podcast.feed_update_minutes, podcast.publish_interval_minutes) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            match release_gap {
+                Some(gap) => (gap / POLLS_PER_RELEASE).clamp(global, MAX_INTERVAL_MINUTES.max(global)),
+                None => global,
+            }
+        }
+    };
+
+    let ttl = podcast.feed_ttl_minutes.unwrap_or(0).min(MAX_INTERVAL_MINUTES);
+    interval.max(ttl).max(MIN_INTERVAL_MINUTES)
+}
+
+// Longest wait between retries of a failing feed.
+const MAX_BACKOFF_MINUTES: i64 = 24 * 60;
+
+pub fn is_due(podcast: &Podcast, settings: &Settings, now: i64) -> bool {
+    let interval = interval_minutes(podcast, settings) as i64;
+    let last_attempt = podcast.last_sync_attempt_at.unwrap_or(0);
+
+    if podcast.sync_failure_count > 0 {
+        return now - last_attempt >= retry_delay_minutes(podcast.sync_failure_count, interval) * 60;
+    }
+    now - podcast.last_synced_at.max(last_attempt) >= interval * 60
+}
+
+// Exponential backoff for failing feeds: the regular interval after the first failure,
+// doubling with each further one.
+fn retry_delay_minutes(failures: i32, interval: i64) -> i64 {
+    let doublings = (failures - 1).clamp(0, 16) as u32;
+    interval
+        .saturating_mul(1 << doublings)
+        .min(MAX_BACKOFF_MINUTES.max(interval))
+}
+
+// Median gap between the most recent episodes, stretched by the time since the latest one so
+// shows that have gone quiet are polled less.
+pub fn publish_interval_minutes(episodes: &[Episode], now: i64) -> Option<i32> {
+    let mut dates: Vec<i64> = episodes
+        .iter()
+        .map(|e| e.publish_date)
+        .filter(|d| *d > 0)
+        .collect();
+    dates.sort_unstable_by(|a, b| b.cmp(a));
+    dates.dedup();
+    dates.truncate(CADENCE_SAMPLE);
+
+    if dates.len() < 3 {
+        return None;
+    }
+
+    let mut gaps: Vec<i64> = dates.windows(2).map(|w| w[0] - w[1]).collect();
+    gaps.sort_unstable();
+    let median = gaps[gaps.len() / 2];
+    let since_latest = now - dates[0];
+
+    Some((median.max(since_latest) / 60).clamp(1, i32::MAX as i64) as i32)
+}
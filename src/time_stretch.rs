@@ -0,0 +1,299 @@
+use rodio::Source;
+use rodio::source::SeekError;
+use std::collections::VecDeque;
+use std::num::NonZero;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+// Output is built from overlapping segments of this length: each block crossfades the end of
+// the previous segment into the start of the next, HOP_MS long.
+const HOP_MS: u32 = 20;
+// How far either side of its nominal position a segment may start, to line up with the
+// waveform of the previous one.
+const SEEK_MS: u32 = 10;
+// Every n-th frame is compared while searching; plenty for speech and far cheaper.
+const CORRELATION_STRIDE: usize = 2;
+const COARSE_STEP: usize = 4;
+
+/// Tempo and position shared between a `TimeStretchSource` and the player, so speed changes
+/// apply to the playing stream without restarting it.
+pub struct StretchControl {
+    speed: AtomicU32,
+    // Position in the episode, which no longer matches the time played at other speeds.
+    position_micros: AtomicU64,
+}
+
+impl StretchControl {
+    pub fn new(speed: f32) -> Self {
+        Self {
+            speed: AtomicU32::new(speed.to_bits()),
+            position_micros: AtomicU64::new(0),
+        }
+    }
+
+    pub fn set_speed(&self, speed: f32) {
+        self.speed.store(speed.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn speed(&self) -> f32 {
+        f32::from_bits(self.speed.load(Ordering::Relaxed))
+    }
+
+    pub fn position(&self) -> Duration {
+        Duration::from_micros(self.position_micros.load(Ordering::Relaxed))
+    }
+
+    fn set_position(&self, position: Duration) {
+        self.position_micros
+            .store(position.as_micros() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Wraps any `rodio::Source` to change its tempo without changing its pitch (WSOLA).
+///
+/// The input is cut into segments that overlap by half. At speed `s` each segment starts
+/// `s` hops further into the input than the last, and the output crossfades one segment into
+/// the next a hop at a time. Within a small window around that nominal start, the segment
+/// start that best matches the previous segment's natural continuation is picked, so the
+/// crossfade joins two similar waveforms instead of smearing the pitch.
+///
+/// At 1× each segment simply continues the previous one, so the audio passes through
+/// unchanged.
+pub struct TimeStretchSource {
+    inner: Box<dyn Source<Item = f32> + Send + 'static>,
+    control: Arc<StretchControl>,
+    channels: NonZero<u16>,
+    sample_rate: NonZero<u32>,
+    // Frames per hop, and the search radius, at this sample rate.
+    hop: usize,
+    seek: usize,
+    // Interleaved input not yet consumed; `input[0]` is frame `input_start` of the stream.
+    input: Vec<f32>,
+    input_start: u64,
+    inner_done: bool,
+    // Start of the previous segment and of the next one's nominal position, in frames
+    // relative to `input[0]`.
+    prev_segment: Option<usize>,
+    nominal: f64,
+    output: VecDeque<f32>,
+}
+
+impl TimeStretchSource {
+    pub fn new(
+        inner: Box<dyn Source<Item = f32> + Send + 'static>,
+        control: Arc<StretchControl>,
+    ) -> Self {
+        let channels = inner.channels();
+        let sample_rate = inner.sample_rate();
+        let frames_per_ms = sample_rate.get() as usize / 1000;
+        Self {
+            inner,
+            control,
+            channels,
+            sample_rate,
+            hop: (HOP_MS as usize * frames_per_ms).max(1),
+            seek: SEEK_MS as usize * frames_per_ms,
+            input: Vec::new(),
+            input_start: 0,
+            inner_done: false,
+            prev_segment: None,
+            nominal: 0.0,
+            output: VecDeque::new(),
+        }
+    }
+
+    fn frames(&self) -> usize {
+        self.input.len() / self.channels.get() as usize
+    }
+
+    // Reads from the inner source until `frames` frames are buffered or it runs out.
+    fn fill(&mut self, frames: usize) {
+        let wanted = frames * self.channels.get() as usize;
+        while !self.inner_done && self.input.len() < wanted {
+            match self.inner.next() {
+                Some(sample) => self.input.push(sample),
+                None => self.inner_done = true,
+            }
+        }
+    }
+
+    // Appends the next hop of output. False once the input is used up.
+    fn produce(&mut self) -> bool {
+        let channels = self.channels.get() as usize;
+        let hop = self.hop;
+        let speed = self.control.speed().clamp(0.25, 4.0) as f64;
+
+        let Some(prev) = self.prev_segment else {
+            // The first hop is the input as-is.
+            self.fill(hop);
+            let frames = self.frames().min(hop);
+            if frames == 0 {
+                return false;
+            }
+            self.output.extend(&self.input[..frames * channels]);
+            self.prev_segment = Some(0);
+            self.nominal = hop as f64 * speed;
+            self.publish_position(0);
+            return true;
+        };
+
+        // The previous segment's second half, which the next segment is matched against.
+        let continuation = prev + hop;
+        let nominal = self.nominal.round() as usize;
+        let (lo, hi) = if (speed - 1.0).abs() < 1e-3 {
+            (continuation, continuation)
+        } else {
+            (nominal.saturating_sub(self.seek), nominal + self.seek)
+        };
+        self.fill((hi + hop).max(continuation + hop));
+
+        let available = self.frames();
+        if available <= continuation {
+            return false;
+        }
+        // Near the end of the input, play out what is left of the previous segment.
+        if available < continuation + hop || available < hi + hop {
+            self.output
+                .extend(&self.input[continuation * channels..available * channels]);
+            self.prev_segment = Some(available);
+            self.nominal = available as f64;
+            self.publish_position(continuation);
+            return true;
+        }
+
+        let start = if lo == hi {
+            lo
+        } else {
+            self.best_match(continuation, lo, hi)
+        };
+
+        // Raised-cosine crossfade from the continuation into the new segment.
+        for i in 0..hop {
+            let fade_in = 0.5 - 0.5 * (std::f32::consts::PI * i as f32 / hop as f32).cos();
+            for c in 0..channels {
+                let old = self.input[(continuation + i) * channels + c];
+                let new = self.input[(start + i) * channels + c];
+                self.output.push_back(old + (new - old) * fade_in);
+            }
+        }
+        self.prev_segment = Some(start);
+        self.nominal = if lo == hi {
+            (start + hop) as f64
+        } else {
+            self.nominal + hop as f64 * speed
+        };
+        self.publish_position(start);
+        self.discard_consumed();
+        true
+    }
+
+    // The segment start in `lo..=hi` whose first hop correlates best with the continuation.
+    // Candidates are tried on a coarse grid first, then around the best of those.
+    fn best_match(&self, continuation: usize, lo: usize, hi: usize) -> usize {
+        let channels = self.channels.get() as usize;
+        // Channels are summed, so stereo and mono are handled the same.
+        let end = hi.max(continuation) + self.hop;
+        let mono: Vec<f32> = self.input[..end * channels]
+            .chunks_exact(channels)
+            .map(|frame| frame.iter().sum())
+            .collect();
+        let template = &mono[continuation..continuation + self.hop];
+
+        let score = |start: usize| -> f32 {
+            let (mut dot, mut energy) = (0.0f32, 0.0f32);
+            for (t, s) in template
+                .iter()
+                .zip(&mono[start..start + self.hop])
+                .step_by(CORRELATION_STRIDE)
+            {
+                dot += s * t;
+                energy += s * s;
+            }
+            dot / (energy.sqrt() + 1e-6)
+        };
+        let best_of = |candidates: &mut dyn Iterator<Item = usize>| -> usize {
+            candidates
+                .map(|start| (start, score(start)))
+                .fold(
+                    (lo, f32::MIN),
+                    |best, c| if c.1 > best.1 { c } else { best },
+                )
+                .0
+        };
+
+        let coarse = best_of(&mut (lo..=hi).step_by(COARSE_STEP));
+        best_of(
+            &mut (coarse.saturating_sub(COARSE_STEP - 1).max(lo)
+                ..=(coarse + COARSE_STEP - 1).min(hi)),
+        )
+    }
+
+    // Drops input that no later segment can start in.
+    fn discard_consumed(&mut self) {
+        let Some(prev) = self.prev_segment else {
+            return;
+        };
+        let keep_from = prev.min((self.nominal as usize).saturating_sub(self.seek));
+        if keep_from == 0 {
+            return;
+        }
+        self.input.drain(..keep_from * self.channels.get() as usize);
+        self.input_start += keep_from as u64;
+        self.prev_segment = Some(prev - keep_from);
+        self.nominal -= keep_from as f64;
+    }
+
+    fn publish_position(&self, frame: usize) {
+        let frames = self.input_start + frame as u64;
+        self.control.set_position(Duration::from_secs_f64(
+            frames as f64 / self.sample_rate.get() as f64,
+        ));
+    }
+}
+
+impl Iterator for TimeStretchSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        loop {
+            if let Some(sample) = self.output.pop_front() {
+                return Some(sample);
+            }
+            if !self.produce() {
+                return None;
+            }
+        }
+    }
+}
+
+impl Source for TimeStretchSource {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> NonZero<u16> {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> NonZero<u32> {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        // Depends on the speed, which may change at any time.
+        None
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.inner.try_seek(pos)?;
+        self.input.clear();
+        self.input_start = (pos.as_secs_f64() * self.sample_rate.get() as f64) as u64;
+        self.inner_done = false;
+        self.prev_segment = None;
+        self.nominal = 0.0;
+        self.output.clear();
+        self.control.set_position(pos);
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use crate::ports::{HttpError, HttpFetcher, HttpRequest, HttpResponse};
+use crate::types::Settings;
+
+// Analytics services that sit in front of the real enclosure URL, in the form
+// `host/path-segment/...` followed by the target host and path. A `*` segment matches any
+// single segment, a trailing `*` matches any segment with that prefix, and a leading `*.`
+// on the host matches any subdomain.
+const BUILTIN_RULES: &[&str] = &[
+    "dts.podtrac.com/redirect.*",
+    "*.podtrac.com/pts/redirect.*",
+    "podtrac.com/pts/redirect.*",
+    "chtbl.com/track/*",
+    "chrt.fm/track/*",
+    "pdst.fm/e",
+    "op3.dev/e",
+    "op3.dev/e,*",
+    "pfx.vpixl.com/*",
+    "mgln.ai/e/*",
+    "arttrk.com/p/*",
+    "verifi.podscribe.com/rss/p",
+    "pscrb.fm/rss/p",
+    "claritaspod.com/measure",
+    "clrtpod.com/m",
+    "tracking.swap.fm/track/*",
+    "prfx.byspotify.com/e",
+    "pdcn.co/e",
+    "prefix.up.audio/s",
+    // media.blubrry.com/<show>/<host>/<path>: the show keyword comes before the target.
+    "media.blubrry.com/*",
+];
+
+// Prefixes are often chained (podtrac -> chartable -> host); give up after this many.
+const MAX_STRIPPED_PREFIXES: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    host: String,
+    segments: Vec<String>,
+}
+
+impl Rule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let line = line
+            .strip_prefix("https://")
+            .or_else(|| line.strip_prefix("http://"))
+            .unwrap_or(line);
+        let mut parts = line.split('/').filter(|s| !s.is_empty());
+        let host = parts.next()?.to_lowercase();
+        if !host.contains('.') {
+            return None;
+        }
+        Some(Self {
+            host,
+            segments: parts.map(str::to_string).collect(),
+        })
+    }
+
+    fn matches_host(&self, host: &str) -> bool {
+        match self.host.strip_prefix("*.") {
+            Some(domain) => host.ends_with(&format!(".{domain}")),
+            None => host == self.host,
+        }
+    }
+}
+
+fn matches_segment(pattern: &str, segment: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => segment.starts_with(prefix),
+        None => pattern == segment,
+    }
+}
+
+/// Rewrites enclosure URLs that go through analytics redirectors (Podtrac, Chartable,
+/// Podsights and friends) to point straight at the media host, so those services never
+/// see the request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackingPrefixes {
+    rules: Vec<Rule>,
+}
+
+impl TrackingPrefixes {
+    // Built-in rules plus the user's own, one per line (`#` starts a comment).
+    pub fn new(custom_rules: &str) -> Self {
+        let custom = custom_rules
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or_default())
+            .filter_map(Rule::parse);
+        Self {
+            rules: BUILTIN_RULES
+                .iter()
+                .filter_map(|r| Rule::parse(r))
+                .chain(custom)
+                .collect(),
+        }
+    }
+
+    // None when prefix stripping is turned off.
+    pub fn from_settings(settings: &Settings) -> Option<Self> {
+        settings
+            .strip_tracking_prefixes
+            .then(|| Self::new(&settings.tracking_prefix_rules))
+    }
+
+    // The direct media URL, or None if `url` doesn't go through a known prefix.
+    pub fn strip(&self, url: &str) -> Option<String> {
+        let mut current = url.to_string();
+        for _ in 0..MAX_STRIPPED_PREFIXES {
+            match self.strip_one(&current) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        (current != url).then_some(current)
+    }
+
+    fn strip_one(&self, url: &str) -> Option<String> {
+        let parsed = url::Url::parse(url).ok()?;
+        let host = parsed.host_str()?.to_lowercase();
+        let segments: Vec<&str> = parsed.path().split('/').skip(1).collect();
+
+        // A rule whose rewrite doesn't come out as a URL doesn't stop a later one matching.
+        self.rules
+            .iter()
+            .filter(|rule| {
+                rule.matches_host(&host)
+                    && rule.segments.len() < segments.len()
+                    && rule
+                        .segments
+                        .iter()
+                        .zip(&segments)
+                        .all(|(pattern, segment)| matches_segment(pattern, segment))
+            })
+            .find_map(|rule| rewrite(&parsed, &segments[rule.segments.len()..]))
+    }
+}
+
+// Builds the target URL from the path left after a prefix, e.g. `host/path` or
+// `https:/host/path`.
+fn rewrite(parsed: &url::Url, rest: &[&str]) -> Option<String> {
+    let rest = rest.join("/");
+    let target = if let Some(path) = rest.strip_prefix("https:/") {
+        format!("https://{}", path.trim_start_matches('/'))
+    } else if let Some(path) = rest.strip_prefix("http:/") {
+        format!("http://{}", path.trim_start_matches('/'))
+    } else {
+        format!("{}://{}", parsed.scheme(), rest)
+    };
+
+    // Only accept the rewrite if what's left still looks like a URL on a real host.
+    let mut target = url::Url::parse(&target).ok()?;
+    if !target.host_str().is_some_and(|h| h.contains('.')) {
+        return None;
+    }
+    // The redirectors pass the query string through to the media host.
+    if target.query().is_none() {
+        target.set_query(parsed.query());
+    }
+    Some(target.to_string())
+}
+
+// Sends `request`, trying the direct media URL first when prefix stripping is on. Falls back
+// to the original URL if the direct one fails, since some hosts only serve via the redirect.
+pub fn get_direct(
+    http: &dyn HttpFetcher,
+    request: HttpRequest,
+    prefixes: Option<&TrackingPrefixes>,
+) -> Result<HttpResponse, HttpError> {
+    if let Some(direct) = prefixes.and_then(|p| p.strip(&request.url)) {
+        let direct_request = HttpRequest {
+            url: direct.clone(),
+            ..request.clone()
+        };
+        match http.get(direct_request).and_then(|r| r.error_for_status()) {
+            Ok(response) => return Ok(response),
+            Err(e) => eprintln!("[privacy] direct fetch of {direct} failed ({e}), using original URL"),
+        }
+    }
+    http.get(request)?.error_for_status()
+}
@@ -56,8 +56,14 @@ pub struct PodcastPreferences {
     pub speed_preset: Option<f32>,
     pub auto_download: Option<bool>,
     pub keep_episodes_count: Option<i32>,
+    // Retention rules; Some(0) turns a rule off for this show.
+    pub delete_played_after_days: Option<i32>,
+    pub delete_older_than_days: Option<i32>,
+    pub keep_unplayed: Option<bool>,
     pub skip_intro_seconds: i32,
     pub skip_outro_seconds: i32,
+    // None = adaptive, based on the global interval and the feed's cadence.
+    pub sync_interval_minutes: Option<i32>,
 }
 
 // Theme
@@ -77,15 +83,53 @@ pub struct Settings {
     pub skip_backward_seconds: i32,
     pub skip_forward_seconds: i32,
     pub sync_interval_minutes: i32,
+    // Feeds fetched at once during background sync
+    pub sync_concurrency: i32,
     pub auto_play_next: bool,
+    // Fetch the next queued episode this many minutes before the current one ends; 0 = off
+    pub prefetch_minutes: i32,
+    // Episodes larger than this (in MB) are not prefetched
+    pub prefetch_max_mb: i32,
+    // Audio cache budgets in MB: held in memory, then spilled to disk
+    pub audio_cache_memory_mb: i32,
+    pub audio_cache_disk_mb: i32,
     pub download_directory: String,
     pub home_density: HomeDensity,
     // Playback defaults
     pub default_speed: f32,
     pub trim_silence_mode: TrimSilenceMode,
+    // Change speed by time stretching, keeping voices at their natural pitch
+    pub preserve_pitch: bool,
     // Download / retention
     pub auto_download_new_episodes: bool,
+    // Hold automatic downloads until this local time window, in minutes after midnight.
+    // The window may wrap past midnight (e.g. 22:00-06:00).
+    pub auto_download_window: bool,
+    pub auto_download_window_start: i32,
+    pub auto_download_window_end: i32,
+    // Combined download speed cap in KB/s; 0 = unlimited
+    pub download_bandwidth_kbps: i32,
+    // Apply the cap to downloads started by hand too, not only automatic ones
+    pub limit_manual_downloads: bool,
     pub global_keep_episodes_count: i32,
+    // Delete a download this many days after it was played; 0 = never
+    pub retention_played_after_days: i32,
+    // Delete downloads this many days after they finished downloading; 0 = never
+    pub retention_older_than_days: i32,
+    // Unplayed downloads are exempt from the rules above
+    pub retention_keep_unplayed: bool,
+    // Episodes downloaded at once; the rest wait in the queue
+    pub download_concurrency: i32,
+    // Where downloads are saved, e.g. `{podcast}/{yyyy}-{mm}-{dd} {title}.{ext}`
+    pub download_name_template: String,
+    // Tag finished downloads with title, artwork and chapters
+    pub write_download_tags: bool,
+    // Disk space downloads may use, in GB; 0 = no limit
+    pub download_quota_gb: i32,
+    // Skip analytics redirectors (Podtrac, Chartable, ...) and fetch audio directly
+    pub strip_tracking_prefixes: bool,
+    // Extra prefixes to strip, one per line
+    pub tracking_prefix_rules: String,
     // Hotkeys
     pub hotkeys: HotkeySettings,
     // Notifications
@@ -93,6 +137,48 @@ pub struct Settings {
     pub notify_download_complete: bool,
     // Appearance
     pub theme: ThemeMode,
+    // Network (empty strings = default)
+    pub http_proxy: String,
+    pub http_user_agent: String,
+    pub http_connect_timeout_secs: i32,
+    pub http_read_timeout_secs: i32,
+    pub http_ca_bundle_path: String,
+}
+
+impl Settings {
+    // None when downloads may use any amount of space.
+    pub fn download_quota_bytes(&self) -> Option<u64> {
+        (self.download_quota_gb > 0).then(|| self.download_quota_gb as u64 * 1024 * 1024 * 1024)
+    }
+
+    // None when prefetching is off.
+    pub fn prefetch_limit_bytes(&self) -> Option<u64> {
+        (self.prefetch_minutes > 0 && self.prefetch_max_mb > 0)
+            .then(|| self.prefetch_max_mb as u64 * 1024 * 1024)
+    }
+
+    // (memory, disk) byte budgets for the audio cache.
+    pub fn audio_cache_budgets(&self) -> (u64, u64) {
+        let mb = |v: i32| v.max(0) as u64 * 1024 * 1024;
+        (mb(self.audio_cache_memory_mb), mb(self.audio_cache_disk_mb))
+    }
+
+    // None when downloads may go at full speed.
+    pub fn download_rate_limit(&self) -> Option<u64> {
+        (self.download_bandwidth_kbps > 0).then(|| self.download_bandwidth_kbps as u64 * 1024)
+    }
+
+    // Whether automatic downloads may start at `minute` (local time, minutes after midnight).
+    pub fn allows_auto_download_at(&self, minute: i32) -> bool {
+        let (start, end) = (self.auto_download_window_start, self.auto_download_window_end);
+        if !self.auto_download_window || start == end {
+            true
+        } else if start < end {
+            (start..end).contains(&minute)
+        } else {
+            minute >= start || minute < end
+        }
+    }
 }
 
 impl Default for Settings {
@@ -102,7 +188,12 @@ impl Default for Settings {
             skip_backward_seconds: 15,
             skip_forward_seconds: 15,
             sync_interval_minutes: 30,
+            sync_concurrency: 4,
             auto_play_next: true,
+            prefetch_minutes: 5,
+            prefetch_max_mb: 200,
+            audio_cache_memory_mb: 256,
+            audio_cache_disk_mb: 2048,
             download_directory: dirs::data_local_dir()
                 .unwrap()
                 .to_str()
@@ -111,12 +202,32 @@ impl Default for Settings {
             home_density: HomeDensity::Grid,
             default_speed: 1.0,
             trim_silence_mode: TrimSilenceMode::Off,
+            preserve_pitch: true,
             auto_download_new_episodes: false,
+            auto_download_window: false,
+            auto_download_window_start: 60,
+            auto_download_window_end: 6 * 60,
+            download_bandwidth_kbps: 0,
+            limit_manual_downloads: false,
             global_keep_episodes_count: 0,
+            retention_played_after_days: 0,
+            retention_older_than_days: 0,
+            retention_keep_unplayed: false,
+            download_concurrency: 2,
+            download_name_template: crate::download_naming::DEFAULT_TEMPLATE.to_string(),
+            write_download_tags: false,
+            download_quota_gb: 0,
+            strip_tracking_prefixes: false,
+            tracking_prefix_rules: String::new(),
             hotkeys: HotkeySettings::default(),
             notify_new_episodes: true,
             notify_download_complete: true,
             theme: ThemeMode::default(),
+            http_proxy: String::new(),
+            http_user_agent: String::new(),
+            http_connect_timeout_secs: 15,
+            http_read_timeout_secs: 30,
+            http_ca_bundle_path: String::new(),
         }
     }
 }
@@ -139,3 +250,30 @@ pub struct QueueDisplayItem {
     pub episode_title: String,
     pub podcast_title: String,
 }
+
+// Disk space taken by one podcast's downloads.
+#[derive(Debug, Clone)]
+pub struct PodcastStorage {
+    pub podcast_id: i32,
+    pub podcast_title: String,
+    pub files: usize,
+    pub bytes: u64,
+    // Part of `bytes` taken by played, unprotected episodes.
+    pub played_bytes: u64,
+}
+
+// What the audio cache holds, for the storage section of the settings page.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AudioCacheStats {
+    pub memory_entries: usize,
+    pub memory_bytes: u64,
+    pub disk_entries: usize,
+    pub disk_bytes: u64,
+}
+
+// A file in a podcast's download folder that no episode accounts for.
+#[derive(Debug, Clone)]
+pub struct UnknownFile {
+    pub path: std::path::PathBuf,
+    pub bytes: u64,
+}
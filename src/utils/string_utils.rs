@@ -93,3 +93,34 @@ fn truncate_to_255(s: &mut String) {
         }
     }
 }
+
+// Human-readable byte count, e.g. "1.4 MB".
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes.max(0) as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes.max(0))
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+// Retention period for sliders, where 0 means the rule is off.
+pub fn format_days(days: i32) -> String {
+    match days {
+        0 => "Never".to_string(),
+        1 => "1 day".to_string(),
+        _ => format!("{days} days"),
+    }
+}
+
+// Minutes after midnight as "HH:MM".
+pub fn format_time_of_day(minutes: i32) -> String {
+    let minutes = minutes.rem_euclid(24 * 60);
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}